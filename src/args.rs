@@ -1,4 +1,8 @@
-use clap::{Parser, ValueEnum};
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+
+use crate::solvers::neighbourhood::Neighbourhood;
 
 #[derive(Parser)]
 #[command(
@@ -6,8 +10,130 @@ use clap::{Parser, ValueEnum};
     version,
     about = "CLI program to solve Lights out puzzle",
     long_about = "CLI program created in Rust to solve Lights out puzzle. It finds the minimal solution and you as well run in simulation mode to check that the board is going to look after a number of steps",
-    next_line_help = false
+    next_line_help = false,
+    args_conflicts_with_subcommands = true
 )]
+pub struct Cli {
+    #[command(subcommand)]
+    pub action: Option<Action>,
+    /// Without a command the board is solved
+    #[command(flatten)]
+    pub solve: Solve,
+}
+
+impl Cli {
+    /// The command to run, without one it solves the board
+    #[must_use]
+    pub fn into_action(self) -> Action {
+        self.action.unwrap_or(Action::Solve(self.solve))
+    }
+}
+
+#[derive(Subcommand)]
+pub enum Action {
+    /// Finds the minimal solution of the board, the default when no command is given
+    Solve(Solve),
+    /// Presses the given lights and shows how the board ends up
+    Simulate(Simulate),
+    /// Creates a random board that can be solved
+    Generate(Generate),
+    /// Shows the rank and the kernel of the board and tells if it can be solved
+    Analyze(Input),
+    /// Solves the board by hand pressing the lights read from the standard input
+    Play(Input),
+}
+
+impl Action {
+    #[must_use]
+    pub fn input(&self) -> &Input {
+        match self {
+            Action::Solve(solve) => &solve.input,
+            Action::Simulate(simulate) => &simulate.input,
+            Action::Generate(generate) => &generate.input,
+            Action::Analyze(input) | Action::Play(input) => input,
+        }
+    }
+}
+
+#[derive(Args)]
+pub struct Solve {
+    #[command(flatten)]
+    pub input: Input,
+    #[command(flatten)]
+    pub options: SolveOptions,
+    /// Run a simulation with the given input
+    ///
+    /// Range from 1 to [cols]*[rows] of the positions to toggle
+    #[arg(short, long, value_name = "STEPS", num_args = 1..)]
+    pub simulation_steps: Vec<usize>,
+    /// Draw the board after every step of the simulation next to each other
+    #[arg(long, default_value_t = false, requires = "simulation_steps")]
+    pub trace: bool,
+    /// Solve the board left by the simulation
+    ///
+    /// The board is shown before its solution
+    #[arg(long, default_value_t = false, requires = "simulation_steps")]
+    pub then_solve: bool,
+}
+
+#[derive(Args)]
+pub struct Simulate {
+    #[command(flatten)]
+    pub input: Input,
+    /// Positions to toggle one after the other
+    ///
+    /// Range from 1 to [cols]*[rows]
+    #[arg(short, long, value_name = "STEPS", num_args = 1.., required = true)]
+    pub simulation_steps: Vec<usize>,
+    /// Draw the board after every step next to each other
+    #[arg(long, default_value_t = false)]
+    pub trace: bool,
+}
+
+#[derive(Args)]
+pub struct Generate {
+    #[command(flatten)]
+    pub input: Input,
+    /// Number of random presses made on the solved board
+    ///
+    /// By default half of the lights
+    #[arg(long, value_name = "PRESSES")]
+    pub presses: Option<usize>,
+    /// Seed of the random presses, the same seed always gives the same board
+    #[arg(long, value_name = "SEED")]
+    pub seed: Option<u64>,
+}
+
+/// Options only used when solving the board
+#[derive(Args, Debug, Clone, Copy)]
+pub struct SolveOptions {
+    /// Algorithm used to find the solution
+    ///
+    /// By default the first solver able to handle the board is used
+    #[arg(long, value_enum, value_name = "ALGORITHM")]
+    pub solver: Option<SolverKind>,
+    /// Print every solution instead of only the minimal one
+    ///
+    /// Shows as well the rank of the toggle matrix, the kernel dimension and the number of solutions
+    #[arg(short, long, default_value_t = false, conflicts_with = "solver")]
+    pub all_solutions: bool,
+    /// Order of the presses in the solution
+    ///
+    /// The lights can be pressed in any order, except with the lit-only solver
+    #[arg(long, value_enum, value_name = "ORDER", default_value_t = PressOrder::Index, conflicts_with = "all_solutions")]
+    pub order: PressOrder,
+    /// How the distance between 2 lights is measured when ordering by travel
+    #[arg(long, value_enum, value_name = "METRIC", default_value_t = Distance::Manhattan)]
+    pub distance: Distance,
+    /// Light where the hand or the robot is before the first press when ordering by travel
+    ///
+    /// Range from 1 to [cols]*[rows]
+    #[arg(long, value_name = "LIGHT", default_value_t = 1)]
+    pub start: usize,
+}
+
+/// Description of the board and how to show it
+#[derive(Args)]
 pub struct Input {
     /// Indexes of the active lights
     ///
@@ -17,23 +143,103 @@ pub struct Input {
     /// The number of rows
     ///
     /// Minimun allowed value: 1
-    #[arg(short, long, default_value_t = 3)]
+    #[arg(short, long, default_value_t = 3, value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
     pub rows: usize,
     /// The number of columns
     ///  
     /// Minimun allowed value: 1
-    #[arg(short, long, default_value_t = 3)]
+    #[arg(short, long, default_value_t = 3, value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
     pub cols: usize,
     /// Enable the debug logs
     ///
     /// Default: false
     #[arg(short, long, default_value_t = false)]
     pub verbose: bool,
-    /// Run a simulation with the given input
+    /// Indexes of the lights that must be on once the puzzle is solved
     ///
-    /// Range from 1 to [cols]*[rows] of the positions to toggle
-    #[arg(short, long, value_name = "STEPS")]
-    pub simulation_steps: Vec<usize>,
+    /// Range from 1 to [cols]*[rows] separated by commas (eg: "7,9"), all the lights are on by default and without values all are off
+    #[arg(short, long, value_name = "LIGHTS", num_args = 0..=1, value_delimiter = ',')]
+    pub target: Option<Vec<usize>>,
+    /// Indexes of the lights that can end up either on or off
+    ///
+    /// Range from 1 to [cols]*[rows] separated by commas (eg: "1,2")
+    #[arg(short = 'x', long, value_name = "LIGHTS", value_delimiter = ',')]
+    pub dont_care: Vec<usize>,
+    /// Indexes of the lights that can't be pressed
+    ///
+    /// Range from 1 to [cols]*[rows] separated by commas (eg: "5,7")
+    #[arg(long, value_name = "LIGHTS", value_delimiter = ',')]
+    pub forbid: Vec<usize>,
+    /// Indexes of the only lights that can be pressed, the rest are forbidden
+    ///
+    /// Range from 1 to [cols]*[rows] separated by commas (eg: "5,7")
+    #[arg(
+        long,
+        value_name = "LIGHTS",
+        value_delimiter = ',',
+        conflicts_with = "forbid"
+    )]
+    pub allow: Option<Vec<usize>>,
+    /// Cost of pressing a light, the solution is the one with the lowest total cost
+    ///
+    /// Every light costs 1 by default (eg: "5=3,6=2" makes the index 5 cost 3 and the index 6 cost 2)
+    #[arg(long, value_name = "INDEX=COST", value_delimiter = ',', value_parser = parse_cost)]
+    pub cost: Vec<(usize, usize)>,
+    /// File with the cost of every light, laid out like the drawing of the board
+    #[arg(long, value_name = "FILE")]
+    pub cost_file: Option<PathBuf>,
+    /// Number of states each light cycles through
+    ///
+    /// With more than 2 states repeating an index in the lights or target increases its value
+    #[arg(long, value_name = "K", default_value_t = 2, value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(2..))]
+    pub states: usize,
+    /// Makes the presses wrap around the edges of the board
+    #[arg(short, long, value_enum, value_name = "EDGES", default_value_t = Wrap::None)]
+    pub wrap: Wrap,
+    /// Shape of the cells of the board
+    #[arg(short, long, value_enum, value_name = "SHAPE", default_value_t = Grid::Square)]
+    pub grid: Grid,
+    /// Number of layers of a 3D board
+    ///
+    /// A press toggles the cells sharing a face with it, the layers are drawn one below the other and the indices go through them like in a single board
+    #[arg(short, long, value_name = "LAYERS", default_value_t = 1, value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+    pub layers: usize,
+    /// Play on the surface of a cube
+    ///
+    /// The 6 faces have [cols]x[rows] cells and are joined by their edges, they are drawn one below the other
+    #[arg(long, default_value_t = false, conflicts_with_all = ["layers", "grid", "wrap", "pattern"])]
+    pub cube: bool,
+    /// Cells toggled by each press
+    ///
+    /// One of plus, x, moore, knight or a list of "col,row" offsets from the pressed cell like "0,0;1,0;-1,0;0,2", the offsets follow the same direction as the indices
+    #[arg(
+        short,
+        long,
+        value_name = "NEIGHBOURHOOD",
+        default_value = "plus",
+        allow_hyphen_values = true
+    )]
+    pub pattern: Neighbourhood,
+    /// File with the cells toggled by each button
+    ///
+    /// Every line is a button followed by the cells it toggles (eg: "1: 1 2 4 5"), the buttons not listed use the pattern
+    #[arg(long, value_name = "FILE")]
+    pub toggles: Option<PathBuf>,
+    /// File with a graph to play on instead of a grid, as an edge list or in DOT format
+    ///
+    /// Pressing a node toggles it and its neighbours, the indices follow the order of the nodes in the file or their number when all of them are numbers
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["rows", "cols", "origin_location", "wrap", "pattern", "toggles", "grid", "layers", "cube"])]
+    pub graph: Option<PathBuf>,
+    /// File with the shape of the board, the spaces are holes and any other character is a cell
+    ///
+    /// The size of the board is taken from the file, the holes are never lit and can't be pressed
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["rows", "cols", "graph", "toggles", "layers", "cube"])]
+    pub shape: Option<PathBuf>,
+    /// File with the board drawn as text, "-" reads it from the standard input
+    ///
+    /// Every line is a row where "#" or "1" is a light on and "." or "0" is off, the boards drawn by simulate and generate can be read back but not the solutions. The size of the board is taken from the file
+    #[arg(short, long, value_name = "FILE", conflicts_with_all = ["lights", "rows", "cols", "graph", "shape", "layers", "cube"])]
+    pub board: Option<PathBuf>,
     /// Sets the way you display the results
     #[arg(short, long, value_enum, value_name="MODE", default_value_t=Display::Draw)]
     pub display_mode: Display,
@@ -44,6 +250,32 @@ pub struct Input {
     pub origin_location: Origin,
 }
 
+impl Input {
+    /// Rows of the whole board, the layers and the faces of the cube are stacked one below the other
+    #[must_use]
+    pub fn total_rows(&self) -> usize {
+        if self.cube {
+            self.rows * 6
+        } else {
+            self.rows * self.layers
+        }
+    }
+}
+
+fn parse_cost(value: &str) -> Result<(usize, usize), String> {
+    let (index, cost) = value
+        .split_once('=')
+        .ok_or_else(|| format!("'{value}' must be 'INDEX=COST'"))?;
+    let parse = |number: &str| {
+        number
+            .trim()
+            .parse::<usize>()
+            .map_err(|err| format!("Invalid number '{number}': {err}"))
+    };
+
+    Ok((parse(index)?, parse(cost)?))
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
 pub enum Display {
     Simple,
@@ -66,3 +298,71 @@ pub enum Origin {
     #[value(name = "tr")]
     TopRight,
 }
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum SolverKind {
+    /// Gaussian elimination over GF(2)
+    Gf2,
+    /// Light chasing, the fastest one for the boards where presses toggle a plus shape
+    Chase,
+    /// Backtracking over every combination, only for small boards
+    Recursive,
+    /// Gaussian elimination modulo the number of states, it doesn't look for the fewest presses
+    Zk,
+    /// Search where only the lit cells can be pressed, the order of the presses matters
+    LitOnly,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum Wrap {
+    /// Presses stop at the edges
+    None,
+    /// Left and right edges are connected
+    Horizontal,
+    /// Top and bottom edges are connected
+    Vertical,
+    /// Every edge is connected, the board is a torus
+    Both,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum PressOrder {
+    /// The order given by the solver
+    Index,
+    /// Shortest path found going through every press from the start light
+    Travel,
+    /// Row by row from the top, every row from left to right
+    Rowwise,
+    /// Row by row from the top, changing direction on every row
+    Snake,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum Distance {
+    /// Moving only along the rows and the columns
+    Manhattan,
+    /// Moving in a straight line
+    Euclidean,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum Grid {
+    /// Square cells, each one with up to 8 cells around
+    Square,
+    /// Hexagonal cells, the odd rows are shifted half a cell to the right
+    Hex,
+    /// Triangular cells, pointing up and down alternately starting with up at the top left
+    Triangle,
+}
+
+impl Wrap {
+    #[must_use]
+    pub fn horizontal(self) -> bool {
+        self == Wrap::Horizontal || self == Wrap::Both
+    }
+
+    #[must_use]
+    pub fn vertical(self) -> bool {
+        self == Wrap::Vertical || self == Wrap::Both
+    }
+}
@@ -0,0 +1,80 @@
+use clap::error::ErrorKind;
+
+use crate::{
+    args::Display,
+    chain_of_responsability::{
+        chainable::Chainable, handler::Handler, state::State, worker::Worker,
+    },
+    define_chainable,
+    solvers::{
+        gf2::{self, Gf2Solver},
+        solver::Solver,
+    },
+};
+
+use super::print::PrintWorker;
+
+define_chainable!(AnalyzeWorker);
+
+impl Handler for AnalyzeWorker {
+    fn handle(&mut self, mut state: State) -> Result<State, clap::error::Error> {
+        let board = state.board.as_deref().expect("Unable to access board");
+
+        if !Gf2Solver.can_solve(board) {
+            return Err(state.command.error(
+                ErrorKind::ArgumentConflict,
+                "Only boards with 2 states and up to 40000 lights can be analyzed",
+            ));
+        }
+
+        let cells = board.cols() * board.rows();
+        let space = gf2::solution_space(board);
+        let count = space.solution_count().map_or_else(
+            || format!("2^{}", space.kernel_dimension()),
+            |count| count.to_string(),
+        );
+
+        println!("Lights: {cells}");
+        println!("Rank: {}", space.rank);
+        println!("Kernel dimension: {}", space.kernel_dimension());
+        println!("Solutions: {count}");
+
+        if let Some(minimal) = space.minimal(board) {
+            let cost = minimal
+                .iter()
+                .map(|&index| board.cost(index))
+                .sum::<usize>();
+            if (0..cells).any(|index| board.cost(index) != 1) {
+                println!("Lowest cost: {cost} with {} presses", minimal.len());
+            } else {
+                println!("Fewest presses: {}", minimal.len());
+            }
+        } else {
+            state.diagnosis = Some(gf2::explain_unsolvable(board));
+        }
+
+        // the kernel are the presses that don't change the board
+        if state.input.display_mode == Display::Draw || state.input.display_mode == Display::All {
+            for (number, vector) in space.kernel.iter().enumerate() {
+                let mut mapped_board = (0..cells)
+                    .map(|index| if board.is_hole(index) { " " } else { "·" }.to_string())
+                    .collect::<Vec<_>>();
+                vector
+                    .iter_ones()
+                    .for_each(|cell| mapped_board[cell] = "o".to_string());
+
+                println!(
+                    "Kernel {}: pressing these lights leaves the board unchanged{}",
+                    number + 1,
+                    board.draw(&mapped_board)
+                );
+            }
+        }
+
+        if let Some(diagnosis) = &state.diagnosis {
+            PrintWorker::print_diagnosis(&state.input, board, diagnosis);
+        }
+
+        Ok(state)
+    }
+}
@@ -0,0 +1,86 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use clap::error::ErrorKind;
+use log::debug;
+
+use crate::{
+    chain_of_responsability::{
+        chainable::Chainable, handler::Handler, state::State, worker::Worker,
+    },
+    define_chainable,
+};
+
+define_chainable!(GenerateWorker, presses: Option<usize>, seed: Option<u64>);
+
+impl GenerateWorker {
+    #[must_use]
+    pub fn new(presses: Option<usize>, seed: Option<u64>) -> Self {
+        Self {
+            next: None,
+            presses,
+            seed,
+        }
+    }
+
+    /// Picks `presses` different cells out of `cells`, the same seed always picks the same ones
+    #[must_use]
+    pub fn random_presses(cells: &[usize], presses: usize, seed: u64) -> Vec<usize> {
+        let mut cells = cells.to_vec();
+        let mut state = seed;
+        let presses = presses.min(cells.len());
+
+        // partial Fisher-Yates shuffle with splitmix64 as the source of randomness
+        for i in 0..presses {
+            state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            let mut random = state;
+            random = (random ^ (random >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            random = (random ^ (random >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            random ^= random >> 31;
+
+            let j = i + (random % (cells.len() - i) as u64) as usize;
+            cells.swap(i, j);
+        }
+        cells.truncate(presses);
+
+        cells
+    }
+}
+
+impl Handler for GenerateWorker {
+    fn handle(&mut self, mut state: State) -> Result<State, clap::error::Error> {
+        if !state.input.lights.is_empty() {
+            return Err(state.command.error(
+                ErrorKind::ArgumentConflict,
+                "The lights of a generated board are random, leave them out",
+            ));
+        }
+
+        let board = state.board.as_deref_mut().expect("Unable to access board");
+        let cols = board.cols();
+        let cells = cols * board.rows();
+
+        // starting from the solved board every random board can be solved
+        for index in 0..cells {
+            if !board.is_masked(index) {
+                board.set(index % cols, index / cols, board.target(index));
+            }
+        }
+
+        let pressable = (0..cells)
+            .filter(|&index| board.is_pressable(index))
+            .collect::<Vec<_>>();
+        let presses = self.presses.unwrap_or(pressable.len() / 2);
+        let seed = self.seed.unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |time| time.as_nanos() as u64)
+        });
+        debug!("Seed: {seed}");
+
+        for index in Self::random_presses(&pressable, presses, seed) {
+            board.trigger_index(index);
+        }
+
+        Ok(state)
+    }
+}
@@ -0,0 +1,290 @@
+use std::{io::Read, path::Path};
+
+use clap::{error::ErrorKind, Command};
+
+use crate::{
+    args::Grid,
+    chain_of_responsability::{
+        chainable::Chainable, handler::Handler, state::State, worker::Worker,
+    },
+    define_chainable,
+    solvers::graph::Graph,
+};
+
+use super::sanitize_input::SanitizeWorker;
+
+define_chainable!(LoadWorker);
+
+/// Board read from its drawing, every index starts from the top left
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct BoardText {
+    pub cols: usize,
+    pub rows: usize,
+    /// Value of every cell, the holes and the don't care cells are 0
+    pub values: Vec<usize>,
+    pub holes: Vec<usize>,
+    pub dont_care: Vec<usize>,
+    pub forbid: Vec<usize>,
+}
+
+impl LoadWorker {
+    /// Parses a toggles definition, every line is a button followed by the cells it toggles
+    /// like `1: 1 2 4 5`. Empty lines and the ones starting with `#` are ignored
+    pub fn parse_toggles(text: &str) -> Result<Vec<(usize, Vec<usize>)>, String> {
+        let mut toggles: Vec<(usize, Vec<usize>)> = vec![];
+        let parse = |value: &str, line: usize| {
+            value
+                .parse::<usize>()
+                .map_err(|err| format!("Line {line}: invalid index '{value}': {err}"))
+        };
+
+        for (number, line) in text.lines().enumerate() {
+            let number = number + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let Some((button, cells)) = line.split_once(':') else {
+                return Err(format!("Line {number}: expected 'button: cells'"));
+            };
+            let button = parse(button.trim(), number)?;
+            let cells = cells
+                .split(|c: char| c.is_whitespace() || c == ',')
+                .filter(|cell| !cell.is_empty())
+                .map(|cell| parse(cell, number))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            if toggles.iter().any(|(defined, _)| *defined == button) {
+                return Err(format!("Line {number}: button {button} is defined twice"));
+            }
+            toggles.push((button, cells));
+        }
+
+        Ok(toggles)
+    }
+
+    /// Parses a shape template, every line is a row where the spaces are holes and any other
+    /// character is a cell. Returns the cols, the rows and the holes of the board
+    pub fn parse_shape(text: &str) -> Result<(usize, usize, Vec<usize>), String> {
+        let lines = text
+            .trim_end()
+            .lines()
+            .map(str::trim_end)
+            .collect::<Vec<_>>();
+        let cols = lines
+            .iter()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or_default();
+        if cols == 0 {
+            return Err("The shape doesn't have any cell".to_string());
+        }
+
+        let mut holes = vec![];
+        for (row, line) in lines.iter().enumerate() {
+            let mut chars = line.chars();
+            for col in 0..cols {
+                if chars.next().unwrap_or(' ') == ' ' {
+                    holes.push(row * cols + col);
+                }
+            }
+        }
+
+        Ok((cols, lines.len(), holes))
+    }
+
+    /// Parses a cost file, the numbers are read row by row from the top left.
+    /// Empty lines and the ones starting with `#` are ignored
+    pub fn parse_costs(text: &str) -> Result<Vec<usize>, String> {
+        text.lines()
+            .map(str::trim)
+            .filter(|line| !line.starts_with('#'))
+            .flat_map(|line| line.split(|c: char| c.is_whitespace() || c == ','))
+            .filter(|cost| !cost.is_empty())
+            .map(|cost| {
+                cost.parse::<usize>()
+                    .map_err(|err| format!("Invalid cost '{cost}': {err}"))
+            })
+            .collect()
+    }
+
+    /// Parses a board drawn as text, each line is a row with one character per cell:
+    /// `#`, `1` or `▲▼` are on, `.`, `·`, `0` or `△▽` are off and higher values are digits
+    /// followed by letters. `?` is a don't care cell, `x` and `X` can't be pressed and spaces
+    /// are holes. The hex grid has a space between the cells and the odd rows start with one.
+    /// The empty line the drawings start with and the ones at the end are skipped, a line
+    /// with only spaces is a row of holes
+    pub fn parse_board(text: &str, grid: Grid) -> Result<BoardText, String> {
+        let mut lines = text.lines().collect::<Vec<_>>();
+        if lines.first().is_some_and(|line| line.is_empty()) {
+            lines.remove(0);
+        }
+        let lines = lines
+            .iter()
+            .rposition(|line| !line.is_empty())
+            .map_or(&[][..], |last| &lines[..=last]);
+
+        let rows = lines
+            .iter()
+            .enumerate()
+            .map(|(row, line)| {
+                let chars = line.chars();
+                if grid == Grid::Hex {
+                    chars.skip(row % 2).step_by(2).collect::<Vec<_>>()
+                } else {
+                    chars.collect()
+                }
+            })
+            .collect::<Vec<_>>();
+        let cols = rows.iter().map(Vec::len).max().unwrap_or_default();
+        if cols == 0 {
+            return Err("The board doesn't have any cell".to_string());
+        }
+
+        let mut board = BoardText {
+            cols,
+            rows: rows.len(),
+            ..BoardText::default()
+        };
+        for (row, cells) in rows.iter().enumerate() {
+            for col in 0..cols {
+                let index = row * cols + col;
+                let value = match cells.get(col).copied().unwrap_or(' ') {
+                    '#' | '▲' | '▼' => 1,
+                    '.' | '·' | '△' | '▽' => 0,
+                    ' ' => {
+                        board.holes.push(index);
+                        0
+                    }
+                    '?' => {
+                        board.dont_care.push(index);
+                        0
+                    }
+                    'x' => {
+                        board.forbid.push(index);
+                        0
+                    }
+                    'X' => {
+                        board.forbid.push(index);
+                        1
+                    }
+                    cell => cell
+                        .to_digit(36)
+                        .map(|value| value as usize)
+                        .ok_or_else(|| format!("Line {}: unknown cell '{cell}'", row + 1))?,
+                };
+                board.values.push(value);
+            }
+        }
+
+        Ok(board)
+    }
+
+    fn read(command: &mut Command, path: &Path) -> Result<String, clap::error::Error> {
+        if path == Path::new("-") {
+            let mut text = String::new();
+            return std::io::stdin()
+                .read_to_string(&mut text)
+                .map(|_| text)
+                .map_err(|err| {
+                    command.error(
+                        ErrorKind::Io,
+                        format!("Unable to read the standard input: {err}"),
+                    )
+                });
+        }
+
+        std::fs::read_to_string(path).map_err(|err| {
+            command.error(
+                ErrorKind::Io,
+                format!("Unable to read {}: {err}", path.display()),
+            )
+        })
+    }
+}
+
+impl Handler for LoadWorker {
+    fn handle(&mut self, mut state: State) -> Result<State, clap::error::Error> {
+        if let Some(path) = state.input.graph.clone() {
+            let graph = Graph::parse(&Self::read(&mut state.command, &path)?)
+                .map_err(|err| state.command.error(ErrorKind::InvalidValue, err))?;
+            if graph.is_empty() {
+                return Err(state
+                    .command
+                    .error(ErrorKind::InvalidValue, "The graph doesn't have any node"));
+            }
+
+            // the nodes are laid out in a single row
+            state.input.cols = graph.len();
+            state.input.rows = 1;
+            state.graph = Some(graph);
+        }
+
+        if let Some(path) = state.input.shape.clone() {
+            let (cols, rows, holes) = Self::parse_shape(&Self::read(&mut state.command, &path)?)
+                .map_err(|err| state.command.error(ErrorKind::InvalidValue, err))?;
+
+            state.input.cols = cols;
+            state.input.rows = rows;
+            state.holes = holes;
+        }
+
+        if let Some(path) = state.input.board.clone() {
+            let board =
+                Self::parse_board(&Self::read(&mut state.command, &path)?, state.input.grid)
+                    .map_err(|err| state.command.error(ErrorKind::InvalidValue, err))?;
+            if let Some(value) = board
+                .values
+                .iter()
+                .find(|&&value| value >= state.input.states)
+            {
+                return Err(state.command.error(
+                    ErrorKind::InvalidValue,
+                    format!(
+                        "The board has a light with value {value} but there are {} states",
+                        state.input.states
+                    ),
+                ));
+            }
+
+            // the rest of workers take the cells like the ones given in the arguments
+            let (cols, rows, origin) = (board.cols, board.rows, state.input.origin_location);
+            let to_input = |mut indices: Vec<usize>| {
+                SanitizeWorker::rotate_light_indices(&mut indices, cols, rows, origin);
+                indices.iter_mut().for_each(|index| *index += 1);
+                indices
+            };
+
+            state.input.cols = cols;
+            state.input.rows = rows;
+            state.input.lights = to_input(
+                board
+                    .values
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(index, &value)| std::iter::repeat_n(index, value))
+                    .collect(),
+            );
+            state.input.dont_care.extend(to_input(board.dont_care));
+            state.input.forbid.extend(to_input(board.forbid));
+            state.holes = board.holes;
+        }
+
+        if let Some(path) = state.input.cost_file.clone() {
+            let costs = Self::parse_costs(&Self::read(&mut state.command, &path)?)
+                .map_err(|err| state.command.error(ErrorKind::InvalidValue, err))?;
+            state.costs = Some(costs);
+        }
+
+        if let Some(path) = state.input.toggles.clone() {
+            let text = Self::read(&mut state.command, &path)?;
+
+            let toggles = Self::parse_toggles(&text)
+                .map_err(|err| state.command.error(ErrorKind::InvalidValue, err))?;
+            state.toggles = Some(toggles);
+        }
+
+        Ok(state)
+    }
+}
@@ -1,3 +1,8 @@
+pub mod analyze;
+pub mod generate;
+pub mod load;
+pub mod order;
+pub mod play;
 pub mod print;
 pub mod sanitize_input;
 pub mod simulator;
@@ -6,9 +11,18 @@ pub mod validate_range;
 
 #[cfg(test)]
 mod impl_chain_tests {
-    use crate::args::Origin;
+    use clap::Parser;
 
-    use super::sanitize_input::SanitizeWorker;
+    use crate::{
+        args::{Action, Cli, Distance, Grid, Origin, PressOrder},
+        chain_of_responsability::{chainable::Chainable, state::State, worker::Worker},
+        solvers::board::{Binary, Board},
+    };
+
+    use super::{
+        generate::GenerateWorker, load::LoadWorker, order::OrderWorker, play::PlayWorker,
+        print::PrintWorker, sanitize_input::SanitizeWorker, validate_range::ValidateRangeWorker,
+    };
 
     #[test]
     fn rotate_3x3() {
@@ -59,4 +73,160 @@ mod impl_chain_tests {
             "Convertion from BR to TL failed"
         );
     }
+
+    #[test]
+    fn parse_toggles() {
+        let text = "# Merlin's Magic Square\n1: 1 2 4 5\n\n2: 1, 2, 3\n5:\n";
+
+        assert_eq!(
+            LoadWorker::parse_toggles(text),
+            Ok(vec![(1, vec![1, 2, 4, 5]), (2, vec![1, 2, 3]), (5, vec![])])
+        );
+        assert!(LoadWorker::parse_toggles("1 2 3").is_err());
+        assert!(LoadWorker::parse_toggles("1: a").is_err());
+        assert!(LoadWorker::parse_toggles("1: 1\n1: 2").is_err());
+    }
+
+    #[test]
+    fn parse_shape() {
+        let text = " ##\n####\n ##  \n\n";
+
+        assert_eq!(LoadWorker::parse_shape(text), Ok((4, 3, vec![0, 3, 8, 11])));
+        assert!(LoadWorker::parse_shape("  \n \n").is_err());
+    }
+
+    #[test]
+    fn parse_costs() {
+        let text = "# corners are cheaper\n1 3 1\n3, 9, 3\n\n1 3 1\n";
+
+        assert_eq!(
+            LoadWorker::parse_costs(text),
+            Ok(vec![1, 3, 1, 3, 9, 3, 1, 3, 1])
+        );
+        assert!(LoadWorker::parse_costs("1 -2").is_err());
+    }
+
+    #[test]
+    fn order_presses() {
+        // 4x4 board with the presses on the corners and the centre of the edges
+        let solution = [0, 1, 3, 12, 14, 15];
+        let order =
+            |order, start| OrderWorker::order(&solution, 4, order, Distance::Manhattan, start);
+
+        assert_eq!(order(PressOrder::Index, 0), solution);
+        assert_eq!(order(PressOrder::Rowwise, 0), [0, 1, 3, 12, 14, 15]);
+        assert_eq!(order(PressOrder::Snake, 0), [0, 1, 3, 15, 14, 12]);
+        assert_eq!(order(PressOrder::Travel, 15), [15, 14, 12, 0, 1, 3]);
+
+        let travel = |presses: &[usize]| OrderWorker::travel(presses, 4, Distance::Manhattan, 0);
+        assert_eq!(travel(&solution), 12.0);
+        assert_eq!(travel(&order(PressOrder::Travel, 0)), 9.0);
+        assert_eq!(
+            OrderWorker::travel(&[5], 4, Distance::Euclidean, 0),
+            2f64.sqrt()
+        );
+    }
+
+    #[test]
+    fn random_presses() {
+        let cells = (0..9).collect::<Vec<_>>();
+        let presses = GenerateWorker::random_presses(&cells, 4, 42);
+
+        assert_eq!(presses, GenerateWorker::random_presses(&cells, 4, 42));
+        assert_eq!(presses.len(), 4);
+        let mut unique = presses.clone();
+        unique.sort_unstable();
+        unique.dedup();
+        assert_eq!(unique.len(), 4);
+
+        assert_eq!(GenerateWorker::random_presses(&cells, 20, 42).len(), 9);
+    }
+
+    #[test]
+    fn parse_presses() {
+        assert_eq!(
+            PlayWorker::parse_presses("1 3, 9", 3, 3, Origin::BottomLeft),
+            Ok(vec![6, 8, 2])
+        );
+        assert_eq!(
+            PlayWorker::parse_presses("1 3, 9", 3, 3, Origin::TopLeft),
+            Ok(vec![0, 2, 8])
+        );
+        assert!(PlayWorker::parse_presses("10", 3, 3, Origin::TopLeft).is_err());
+        assert!(PlayWorker::parse_presses("0", 3, 3, Origin::TopLeft).is_err());
+        assert!(PlayWorker::parse_presses("a", 3, 3, Origin::TopLeft).is_err());
+    }
+
+    #[test]
+    fn side_by_side() {
+        let drawings = [
+            ("Start".to_string(), "\n··\n··".to_string()),
+            ("1: 1".to_string(), "\n #\n##\n##".to_string()),
+        ];
+
+        assert_eq!(
+            PrintWorker::side_by_side(&drawings),
+            "\nStart  1: 1\n··      #\n··     ##\n       ##"
+        );
+    }
+
+    #[test]
+    fn parse_board() {
+        let board = LoadWorker::parse_board("\n#.1\n0?x\n X\n\n", Grid::Square).unwrap();
+
+        assert_eq!((board.cols, board.rows), (3, 3));
+        assert_eq!(board.values, vec![1, 0, 1, 0, 0, 0, 0, 1, 0]);
+        assert_eq!(board.holes, vec![6, 8]);
+        assert_eq!(board.dont_care, vec![4]);
+        assert_eq!(board.forbid, vec![5, 7]);
+
+        assert_eq!(
+            LoadWorker::parse_board("2a", Grid::Square).unwrap().values,
+            vec![2, 10]
+        );
+        // rows and columns made only of holes are part of the board
+        let board = LoadWorker::parse_board("   \n## \n", Grid::Square).unwrap();
+        assert_eq!((board.cols, board.rows), (3, 2));
+        assert_eq!(board.holes, vec![0, 1, 2, 5]);
+
+        assert!(LoadWorker::parse_board("#!", Grid::Square).is_err());
+        assert!(LoadWorker::parse_board("\n\n", Grid::Square).is_err());
+    }
+
+    #[test]
+    fn parse_board_round_trip() {
+        for grid in [Grid::Square, Grid::Hex, Grid::Triangle] {
+            let board = Binary::new_from_positions(&[0, 4, 6, 7, 10], 4, 3)
+                .with_holes(&[3, 8])
+                .with_mask(&[5])
+                .with_forbidden(&[6, 9])
+                .with_grid(grid);
+            let drawing = board.draw(&PrintWorker::board_to_vec(&board));
+
+            let parsed = LoadWorker::parse_board(&drawing, grid).unwrap();
+            assert_eq!((parsed.cols, parsed.rows), (4, 3), "{grid:?}");
+            assert_eq!(parsed.values, board.iter().copied().collect::<Vec<_>>());
+            assert_eq!(parsed.holes, vec![3, 8]);
+            assert_eq!(parsed.dont_care, vec![5]);
+            assert_eq!(parsed.forbid, vec![6, 9]);
+        }
+    }
+
+    #[test]
+    fn start_out_of_range() {
+        for start in ["0", "50"] {
+            let Action::Solve(solve) = Cli::try_parse_from(["<PROGRAM>", "--start", start, "1"])
+                .unwrap()
+                .into_action()
+            else {
+                panic!("the flat form is not a solve");
+            };
+
+            let mut validator = Box::<ValidateRangeWorker>::default();
+            validator.set_next(Box::<SanitizeWorker>::default());
+            let state = State::new(solve.input).with_solve(solve.options);
+
+            assert!(validator.execute(state).is_err(), "start {start} is valid");
+        }
+    }
 }
@@ -0,0 +1,171 @@
+use clap::error::ErrorKind;
+use log::debug;
+
+use crate::{
+    args::{Distance, PressOrder, SolverKind},
+    chain_of_responsability::{
+        chainable::Chainable, handler::Handler, state::State, worker::Worker,
+    },
+    define_chainable,
+};
+
+define_chainable!(OrderWorker);
+
+/// Above this number of presses the travel order is the snake one, looking for a shorter path
+/// grows with the square of the presses
+const MAX_TRAVEL_PRESSES: usize = 2000;
+
+impl OrderWorker {
+    /// Reorders the presses of `solution` on a board with `cols` columns, `start` is the cell
+    /// before the first press
+    #[must_use]
+    pub fn order(
+        solution: &[usize],
+        cols: usize,
+        order: PressOrder,
+        distance: Distance,
+        start: usize,
+    ) -> Vec<usize> {
+        let mut presses = solution.to_vec();
+        let snake_key = |&index: &usize| {
+            let row = index / cols;
+            let col = index % cols;
+            (
+                row,
+                if row.is_multiple_of(2) {
+                    col
+                } else {
+                    cols - col
+                },
+            )
+        };
+
+        match order {
+            PressOrder::Index => {}
+            PressOrder::Rowwise => presses.sort_unstable(),
+            PressOrder::Snake => presses.sort_unstable_by_key(snake_key),
+            PressOrder::Travel if presses.len() > MAX_TRAVEL_PRESSES => {
+                presses.sort_unstable_by_key(snake_key);
+            }
+            PressOrder::Travel => {
+                let distance = |a: usize, b: usize| Self::distance(a, b, cols, distance);
+                presses = Self::nearest_neighbour(&presses, start, distance);
+                Self::two_opt(&mut presses, start, distance);
+            }
+        }
+
+        presses
+    }
+
+    /// Length of the path going through every press in order from `start`
+    #[must_use]
+    pub fn travel(presses: &[usize], cols: usize, distance: Distance, start: usize) -> f64 {
+        std::iter::once(&start)
+            .chain(presses)
+            .zip(presses)
+            .map(|(&a, &b)| Self::distance(a, b, cols, distance))
+            .sum()
+    }
+
+    fn distance(a: usize, b: usize, cols: usize, distance: Distance) -> f64 {
+        let col = (a % cols).abs_diff(b % cols) as f64;
+        let row = (a / cols).abs_diff(b / cols) as f64;
+
+        match distance {
+            Distance::Manhattan => col + row,
+            Distance::Euclidean => col.hypot(row),
+        }
+    }
+
+    /// Path that always moves to the closest press not made yet
+    fn nearest_neighbour(
+        presses: &[usize],
+        start: usize,
+        distance: impl Fn(usize, usize) -> f64,
+    ) -> Vec<usize> {
+        let mut pending = presses.to_vec();
+        let mut path = Vec::with_capacity(presses.len());
+        let mut current = start;
+
+        while !pending.is_empty() {
+            let closest = (0..pending.len())
+                .min_by(|&a, &b| {
+                    distance(current, pending[a]).total_cmp(&distance(current, pending[b]))
+                })
+                .expect("There are pending presses");
+            current = pending.swap_remove(closest);
+            path.push(current);
+        }
+
+        path
+    }
+
+    /// Reverses parts of the path while that makes it shorter, the path starts at `start` and
+    /// doesn't have to come back
+    fn two_opt(path: &mut [usize], start: usize, distance: impl Fn(usize, usize) -> f64) {
+        let mut improved = true;
+
+        while improved {
+            improved = false;
+
+            for i in 0..path.len() {
+                let before = if i == 0 { start } else { path[i - 1] };
+
+                for j in i + 1..path.len() {
+                    let removed = distance(before, path[i])
+                        + path
+                            .get(j + 1)
+                            .map_or(0.0, |&after| distance(path[j], after));
+                    let added = distance(before, path[j])
+                        + path
+                            .get(j + 1)
+                            .map_or(0.0, |&after| distance(path[i], after));
+
+                    if added + f64::EPSILON < removed {
+                        path[i..=j].reverse();
+                        improved = true;
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Handler for OrderWorker {
+    fn handle(&mut self, mut state: State) -> Result<State, clap::error::Error> {
+        let options = state.solve.expect("Unable to access the solve options");
+        let order = options.order;
+        debug!("Press order: {order:?}");
+
+        if order == PressOrder::Index {
+            return Ok(state);
+        }
+        if options.solver == Some(SolverKind::LitOnly) {
+            return Err(state.command.error(
+                ErrorKind::ArgumentConflict,
+                "The presses of the lit-only solver can't be reordered",
+            ));
+        }
+
+        let cols = state
+            .board
+            .as_deref()
+            .expect("Unable to access board")
+            .cols();
+        let (distance, start) = (options.distance, options.start);
+
+        if let Some(solution) = &mut state.solution {
+            debug!(
+                "Travel before ordering: {}",
+                Self::travel(solution, cols, distance, start)
+            );
+            *solution = Self::order(solution, cols, order, distance, start);
+            debug!(
+                "Travel after ordering: {}",
+                Self::travel(solution, cols, distance, start)
+            );
+        }
+
+        Ok(state)
+    }
+}
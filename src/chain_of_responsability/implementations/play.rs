@@ -0,0 +1,77 @@
+use std::io::{BufRead, Write};
+
+use crate::{
+    args::Origin,
+    chain_of_responsability::{
+        chainable::Chainable, handler::Handler, state::State, worker::Worker,
+    },
+    define_chainable,
+};
+
+use super::{print::PrintWorker, sanitize_input::SanitizeWorker};
+
+define_chainable!(PlayWorker);
+
+impl PlayWorker {
+    /// Reads the lights to press from a line like `1 5 9`, returns them starting from the top left
+    pub fn parse_presses(
+        line: &str,
+        cols: usize,
+        rows: usize,
+        origin: Origin,
+    ) -> Result<Vec<usize>, String> {
+        let mut presses = line
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|light| !light.is_empty())
+            .map(|light| match light.parse::<usize>() {
+                Ok(index) if (1..=cols * rows).contains(&index) => Ok(index - 1),
+                Ok(index) => Err(format!(
+                    "Index {index} out of range for a {rows}x{cols} size"
+                )),
+                Err(err) => Err(format!("Invalid light '{light}': {err}")),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        SanitizeWorker::rotate_light_indices(&mut presses, cols, rows, origin);
+
+        Ok(presses)
+    }
+}
+
+impl Handler for PlayWorker {
+    fn handle(&mut self, mut state: State) -> Result<State, clap::error::Error> {
+        let board = state.board.as_deref_mut().expect("Unable to access board");
+        let (cols, rows) = board.size();
+        let mut lines = std::io::stdin().lock().lines();
+        let mut presses = 0;
+
+        loop {
+            println!("{}", board.draw(&PrintWorker::board_to_vec(board)));
+
+            if board.is_solved() {
+                println!("Solved in {presses} presses");
+                break;
+            }
+
+            print!("Lights to press: ");
+            std::io::stdout().flush().ok();
+
+            let Some(Ok(line)) = lines.next() else {
+                println!();
+                break;
+            };
+
+            match Self::parse_presses(&line, cols, rows, state.input.origin_location) {
+                Ok(lights) => {
+                    presses += lights.len();
+                    for light in lights {
+                        board.trigger_index(light);
+                    }
+                }
+                Err(err) => println!("{err}"),
+            }
+        }
+
+        Ok(state)
+    }
+}
@@ -1,13 +1,13 @@
 use log::debug;
 
 use crate::{
-    args::Display,
+    args::{Display, Input},
     chain_of_responsability::{
         chainable::Chainable, handler::Handler, implementations::sanitize_input::SanitizeWorker,
         state::State, worker::Worker,
     },
     define_chainable,
-    solvers::board::Board,
+    solvers::{board::Board, gf2::QuietPattern},
 };
 
 define_chainable!(PrintWorker);
@@ -16,63 +16,266 @@ impl PrintWorker {
     pub fn board_to_vec(board: &(impl Board + ?Sized)) -> Vec<String> {
         board
             .iter()
-            .map(|val| {
-                if *val == 1 {
+            .enumerate()
+            .map(|(index, val)| {
+                if board.is_hole(index) {
+                    " ".to_string()
+                } else if !board.is_pressable(index) {
+                    // forbidden cells keep telling if they are lit
+                    if *val == 0 { "x" } else { "X" }.to_string()
+                } else if board.is_masked(index) {
+                    "?".to_string()
+                } else if *val == 0 {
+                    "·".to_string()
+                } else if board.states() == 2 {
                     "#".to_string()
                 } else {
-                    "·".to_string()
+                    Self::digit(*val)
                 }
             })
             .collect()
     }
 
-    #[must_use]
-    pub fn vec_to_str(board_as_char: &[String], cols: usize) -> String {
-        let mut board_string = String::new();
-        for (index, node) in board_as_char.iter().enumerate() {
-            if index % cols == 0 {
-                board_string.push('\n');
+    fn print_solution(input: &Input, board: &dyn Board, solution: &[usize]) {
+        let display_mode = input.display_mode;
+
+        if display_mode == Display::Simple || display_mode == Display::All {
+            let solution = Self::to_input_indices(input, board, solution);
+
+            println!("{solution:?}");
+        }
+
+        if (display_mode == Display::Draw || display_mode == Display::All) && board.states() > 2 {
+            // with more states a cell can be pressed many times so draw how many
+            let mut presses = vec![0usize; board.cols() * board.rows()];
+            solution.iter().for_each(|&position| presses[position] += 1);
+
+            let mapped_board = presses
+                .iter()
+                .enumerate()
+                .map(|(index, &times)| {
+                    if board.is_hole(index) {
+                        " ".to_string()
+                    } else if times == 0 {
+                        "·".to_string()
+                    } else {
+                        Self::digit(times)
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            println!("{}", board.draw(&mapped_board));
+        } else if display_mode == Display::Draw || display_mode == Display::All {
+            // a cell pressed again starts a new drawing of the board as it is at that point
+            let mut board = board.boxed_clone();
+            let mut rounds = vec![vec![]];
+            for (order, &position) in solution.iter().enumerate() {
+                let round = rounds.last_mut().expect("There is always a round");
+                if round.iter().any(|&(_, pressed)| pressed == position) {
+                    rounds.push(vec![]);
+                }
+                rounds
+                    .last_mut()
+                    .expect("There is always a round")
+                    .push((order, position));
             }
 
-            board_string.push_str(node);
+            for round in rounds {
+                let mut mapped_board = Self::board_to_vec(&*board);
+
+                for &(order, position) in &round {
+                    mapped_board[position] = order.to_string();
+                    board.trigger_index(position);
+                }
+
+                println!("{}", board.draw(&mapped_board));
+            }
         }
 
-        board_string
+        let cells = board.cols() * board.rows();
+        if (0..cells).any(|index| board.cost(index) != 1) {
+            let cost = solution
+                .iter()
+                .map(|&index| board.cost(index))
+                .sum::<usize>();
+            println!("Total cost: {cost}");
+        }
     }
-}
 
-impl Handler for PrintWorker {
-    fn handle(&mut self, state: State) -> Result<State, clap::error::Error> {
-        let display_mode = state.input.display_mode;
-        debug!("Display mode: {:?}", display_mode);
-        let Some(solution) = &state.solution else { return Ok(state) };
-        let board = state.board.as_deref().expect("Unable to access board");
+    /// Single character for a value, after 9 it continues with letters
+    fn digit(value: usize) -> String {
+        u32::try_from(value)
+            .ok()
+            .and_then(|value| char::from_digit(value, 36))
+            .map_or_else(|| "+".to_string(), |digit| digit.to_string())
+    }
+
+    /// Rotates the indices back to the origin location and starts counting them from 1
+    pub fn to_input_indices(input: &Input, board: &dyn Board, cells: &[usize]) -> Vec<usize> {
+        let (cols, rows) = board.size();
+        let mut indices = cells.to_vec();
+
+        SanitizeWorker::rotate_light_indices(&mut indices, cols, rows, input.origin_location);
+        indices.iter_mut().for_each(|val| *val += 1);
+
+        indices
+    }
+
+    /// Lights that are on, repeated as many times as their value, and the drawing of the board.
+    /// With a `trace` of the simulation every board of it is drawn instead
+    fn print_board(input: &Input, board: &dyn Board, trace: &[Box<dyn Board>], steps: &[usize]) {
+        let display_mode = input.display_mode;
 
         if display_mode == Display::Simple || display_mode == Display::All {
-            // need to clone solution bc in display mode 'all' this is going to change the board
-            let mut solution = solution.clone();
-            solution.iter_mut().for_each(|val| *val += 1);
+            let lights = board
+                .iter()
+                .enumerate()
+                .flat_map(|(index, &value)| std::iter::repeat_n(index, value))
+                .collect::<Vec<_>>();
+
+            println!("{:?}", Self::to_input_indices(input, board, &lights));
+        }
+
+        if (display_mode == Display::Draw || display_mode == Display::All) && trace.is_empty() {
+            println!("{}", board.draw(&Self::board_to_vec(board)));
+        } else if display_mode == Display::Draw || display_mode == Display::All {
+            Self::print_trace(input, trace, steps);
+        }
+    }
+
+    /// Joins the drawings line by line so they show up next to each other, each one under its label
+    #[must_use]
+    pub fn side_by_side(drawings: &[(String, String)]) -> String {
+        let columns = drawings
+            .iter()
+            .map(|(label, drawing)| {
+                let mut lines = std::iter::once(label.as_str())
+                    .chain(drawing.lines().skip_while(|line| line.is_empty()))
+                    .collect::<Vec<_>>();
+                let width = lines
+                    .iter()
+                    .map(|line| line.chars().count())
+                    .max()
+                    .unwrap_or_default();
+                lines.iter_mut().for_each(|line| *line = line.trim_end());
+                (lines, width)
+            })
+            .collect::<Vec<_>>();
+        let height = columns
+            .iter()
+            .map(|(lines, _)| lines.len())
+            .max()
+            .unwrap_or_default();
+
+        let mut joined = String::new();
+        for row in 0..height {
+            let line = columns
+                .iter()
+                .map(|(lines, width)| {
+                    let line = lines.get(row).copied().unwrap_or_default();
+                    format!("{line}{}", " ".repeat(width - line.chars().count()))
+                })
+                .collect::<Vec<_>>()
+                .join("  ");
+            joined.push('\n');
+            joined.push_str(line.trim_end());
+        }
 
-            let (cols, rows) = board.size();
+        joined
+    }
+
+    /// Every board of the simulation next to each other labeled with the light pressed to reach it
+    fn print_trace(input: &Input, trace: &[Box<dyn Board>], steps: &[usize]) {
+        let Some(board) = trace.first() else {
+            return;
+        };
+        let steps = Self::to_input_indices(input, &**board, steps);
+
+        let drawings = trace
+            .iter()
+            .enumerate()
+            .map(|(step, board)| {
+                let label = if step == 0 {
+                    "Start".to_string()
+                } else {
+                    format!("{step}: {}", steps[step - 1])
+                };
+                (label, board.draw(&Self::board_to_vec(&**board)))
+            })
+            .collect::<Vec<_>>();
+
+        println!("{}", Self::side_by_side(&drawings));
+    }
 
-            SanitizeWorker::rotate_light_indices(
-                &mut solution,
-                cols,
-                rows,
-                state.input.origin_location,
+    /// Prints every quiet pattern, drawn with `!` on the lights that have to change and `o` on
+    /// the rest of its cells, `x` already stands for the forbidden cells in the boards
+    pub fn print_diagnosis(input: &Input, board: &dyn Board, diagnosis: &[QuietPattern]) {
+        let (cols, rows) = board.size();
+        let to_input_indices = |cells: &[usize]| {
+            let mut indices = Self::to_input_indices(input, board, cells);
+            indices.sort_unstable();
+            indices
+        };
+
+        println!("The board has no solution");
+
+        for (number, pattern) in diagnosis.iter().enumerate() {
+            println!(
+                "Quiet pattern {}: every press toggles an even number of the cells {:?} but the lights {:?} have to change",
+                number + 1,
+                to_input_indices(&pattern.cells),
+                to_input_indices(&pattern.violations),
             );
 
-            println!("{solution:?}");
+            if input.display_mode == Display::Draw || input.display_mode == Display::All {
+                let mut mapped_board = (0..cols * rows)
+                    .map(|index| if board.is_hole(index) { " " } else { "·" }.to_string())
+                    .collect::<Vec<_>>();
+                pattern
+                    .cells
+                    .iter()
+                    .for_each(|&cell| mapped_board[cell] = "o".to_string());
+                pattern
+                    .violations
+                    .iter()
+                    .for_each(|&cell| mapped_board[cell] = "!".to_string());
+
+                println!("{}", board.draw(&mapped_board));
+            }
         }
+    }
+}
 
-        if display_mode == Display::Draw || display_mode == Display::All {
-            let mut mapped_board = Self::board_to_vec(board);
+impl Handler for PrintWorker {
+    fn handle(&mut self, state: State) -> Result<State, clap::error::Error> {
+        debug!("Display mode: {:?}", state.input.display_mode);
+        let board = state.board.as_deref().expect("Unable to access board");
+
+        // nothing solved leaves the board as the result, after a simulation it's shown as well
+        // before its solution
+        let solved = state.solution.is_some() || state.diagnosis.is_some();
+        if !solved || !state.steps.is_empty() {
+            Self::print_board(&state.input, board, &state.trace, &state.steps);
+        }
 
-            for (order, position) in solution.iter().enumerate() {
-                mapped_board[*position] = order.to_string();
+        if let Some(space) = &state.solution_space {
+            let count = space.solution_count().map_or_else(
+                || format!("2^{}", space.kernel_dimension()),
+                |count| count.to_string(),
+            );
+            println!("Rank: {}", space.rank);
+            println!("Kernel dimension: {}", space.kernel_dimension());
+            println!("Solutions: {count}");
+
+            for solution in space.solutions() {
+                Self::print_solution(&state.input, board, &solution);
             }
+        } else if let Some(solution) = &state.solution {
+            Self::print_solution(&state.input, board, solution);
+        }
 
-            println!("{}", Self::vec_to_str(&mapped_board, board.cols()));
+        if let Some(diagnosis) = &state.diagnosis {
+            Self::print_diagnosis(&state.input, board, diagnosis);
         }
 
         Ok(state)
@@ -4,7 +4,11 @@ use crate::{
         chainable::Chainable, handler::Handler, state::State, worker::Worker,
     },
     define_chainable,
-    solvers::board::Binary,
+    solvers::{
+        board::{Binary, Board},
+        cuboid::Cuboid,
+        per_cell::PerCell,
+    },
 };
 
 define_chainable!(SanitizeWorker);
@@ -21,6 +25,13 @@ impl SanitizeWorker {
         };
     }
 
+    /// Every time an index shows up its cell value increases by one
+    fn count_positions(positions: &[usize], len: usize) -> Vec<usize> {
+        let mut values = vec![0usize; len];
+        positions.iter().for_each(|&position| values[position] += 1);
+        values
+    }
+
     fn reorder_cols(indices: &mut [usize], _rows: usize, cols: usize) {
         for index in indices.iter_mut() {
             let col = *index % cols;
@@ -55,21 +66,144 @@ impl SanitizeWorker {
 
 impl Handler for SanitizeWorker {
     fn handle(&mut self, mut state: State) -> Result<State, clap::error::Error> {
-        let rows = state.input.rows;
+        // the layers are stacked as a single board
+        let rows = state.input.total_rows();
         let cols = state.input.cols;
         let origin = state.input.origin_location;
 
+        let states = state.input.states;
+
         let lights = &mut state.input.lights;
-        lights.sort_unstable();
-        lights.dedup();
+        if states == 2 {
+            lights.sort_unstable();
+            lights.dedup();
+        }
         lights.iter_mut().for_each(|val| *val -= 1);
         Self::rotate_light_indices(lights, cols, rows, origin);
 
-        let simulation_steps = &mut state.input.simulation_steps;
-        simulation_steps.iter_mut().for_each(|val| *val -= 1);
-        Self::rotate_light_indices(simulation_steps, cols, rows, origin);
+        if let Some(target) = &mut state.input.target {
+            if states == 2 {
+                target.sort_unstable();
+                target.dedup();
+            }
+            target.iter_mut().for_each(|val| *val -= 1);
+            Self::rotate_light_indices(target, cols, rows, origin);
+        }
+
+        let dont_care = &mut state.input.dont_care;
+        dont_care.iter_mut().for_each(|val| *val -= 1);
+        Self::rotate_light_indices(dont_care, cols, rows, origin);
 
-        state.board = Some(Box::new(Binary::new_from_positions(lights, cols, rows)));
+        let steps = &mut state.steps;
+        steps.iter_mut().for_each(|val| *val -= 1);
+        Self::rotate_light_indices(steps, cols, rows, origin);
+
+        if let Some(options) = &mut state.solve {
+            let mut start = [options.start - 1];
+            Self::rotate_light_indices(&mut start, cols, rows, origin);
+            options.start = start[0];
+        }
+
+        let forbid = &mut state.input.forbid;
+        forbid.iter_mut().for_each(|val| *val -= 1);
+        Self::rotate_light_indices(forbid, cols, rows, origin);
+
+        if let Some(allow) = &mut state.input.allow {
+            allow.iter_mut().for_each(|val| *val -= 1);
+            Self::rotate_light_indices(allow, cols, rows, origin);
+
+            *forbid = (0..cols * rows)
+                .filter(|index| !allow.contains(index))
+                .collect();
+        }
+        let forbidden = &state.input.forbid;
+
+        let mut costs = state.costs.take().unwrap_or_else(|| vec![1; cols * rows]);
+        for &(index, cost) in &state.input.cost {
+            let mut index = [index - 1];
+            Self::rotate_light_indices(&mut index, cols, rows, origin);
+            costs[index[0]] = cost;
+        }
+
+        // the offsets are given in the direction of the indices, flip them like the indices
+        let neighbourhood = state.input.pattern.flipped(
+            matches!(origin, Origin::TopRight | Origin::BottomRight),
+            matches!(origin, Origin::BottomLeft | Origin::BottomRight),
+        );
+
+        if let Some(graph) = state.graph.take() {
+            let mut board = graph
+                .with_lights(lights)
+                .with_mask(dont_care)
+                .with_forbidden(forbidden)
+                .with_costs(&costs);
+            if let Some(target) = &state.input.target {
+                board = board.with_target(target);
+            }
+
+            state.board = Some(Box::new(board));
+        } else if state.input.cube || state.input.layers > 1 {
+            let board = if state.input.cube {
+                Cuboid::new_cube(cols)
+            } else {
+                Cuboid::new_blank(cols, state.input.rows, state.input.layers)
+            };
+            let mut board = board
+                .with_lights(lights)
+                .with_mask(dont_care)
+                .with_forbidden(forbidden)
+                .with_costs(&costs);
+            if let Some(target) = &state.input.target {
+                board = board.with_target(target);
+            }
+
+            state.board = Some(Box::new(board));
+        } else if let Some(toggles) = &state.toggles {
+            // the buttons not listed in the file keep the pattern
+            let grid = Binary::new_blank(cols, rows)
+                .with_wrap(state.input.wrap)
+                .with_neighbourhood(neighbourhood)
+                .with_grid(state.input.grid);
+            let mut cells = (0..cols * rows)
+                .map(|index| grid.toggled_cells(index))
+                .collect::<Vec<_>>();
+            for (button, toggled) in toggles {
+                let mut button = [button - 1];
+                Self::rotate_light_indices(&mut button, cols, rows, origin);
+
+                let mut toggled = toggled.iter().map(|cell| cell - 1).collect::<Vec<_>>();
+                Self::rotate_light_indices(&mut toggled, cols, rows, origin);
+
+                cells[button[0]] = toggled;
+            }
+
+            let mut board = PerCell::new_from_positions(lights, cells, cols, rows)
+                .with_mask(dont_care)
+                .with_forbidden(forbidden)
+                .with_costs(&costs)
+                .with_grid(state.input.grid);
+            if let Some(target) = &state.input.target {
+                board = board.with_target(target);
+            }
+
+            state.board = Some(Box::new(board));
+        } else {
+            let mut board = Binary::new_blank(cols, rows)
+                .with_states(states)
+                .with_values(&Self::count_positions(lights, cols * rows))
+                .with_mask(dont_care)
+                .with_forbidden(forbidden)
+                .with_costs(&costs)
+                .with_holes(&state.holes)
+                .with_wrap(state.input.wrap)
+                .with_neighbourhood(neighbourhood)
+                .with_grid(state.input.grid);
+            if let Some(target) = &state.input.target {
+                board = board.with_target(target);
+            }
+
+            state.board = Some(Box::new(board));
+        }
 
         Ok(state)
     }
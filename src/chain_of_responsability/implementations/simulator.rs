@@ -10,27 +10,41 @@ use crate::{
 
 use super::print::PrintWorker;
 
-define_chainable!(SimulatorWorker);
+define_chainable!(SimulatorWorker, trace: bool);
 
 impl SimulatorWorker {
+    /// With `trace` the board is kept after every step
+    #[must_use]
+    pub fn new(trace: bool) -> Self {
+        Self { next: None, trace }
+    }
+
     fn prettify_board(board: &(impl Board + ?Sized)) -> String {
-        PrintWorker::vec_to_str(&PrintWorker::board_to_vec(board), board.cols())
+        board.draw(&PrintWorker::board_to_vec(board))
     }
 }
 
 impl Handler for SimulatorWorker {
     fn handle(&mut self, mut state: State) -> Result<State, clap::error::Error> {
         let board = state.board.as_deref_mut().expect("Unable to access board");
-        let steps = &state.input.simulation_steps;
+        let steps = &state.steps;
         debug!(
             "Board before the simulation:\n {}",
             Self::prettify_board(board)
         );
         debug!("Steps to simulate: {:?}", steps);
 
+        if self.trace {
+            state.trace.push(board.boxed_clone());
+        }
+
         for (step, node_to_trigger) in steps.iter().enumerate() {
             board.trigger_index(*node_to_trigger);
             debug!("Step {}:\n {}", step, Self::prettify_board(board));
+
+            if self.trace {
+                state.trace.push(board.boxed_clone());
+            }
         }
 
         debug!("Board after simulation: {}", Self::prettify_board(board));
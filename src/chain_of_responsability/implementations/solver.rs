@@ -1,11 +1,15 @@
-use log::debug;
+use clap::error::ErrorKind;
+use log::{debug, warn};
 
 use crate::{
     chain_of_responsability::{
         chainable::Chainable, handler::Handler, state::State, worker::Worker,
     },
     define_chainable,
-    solvers::gf2,
+    solvers::{
+        gf2::{self, Gf2Solver},
+        solver::{self, Solver},
+    },
 };
 
 define_chainable!(SolverWorker);
@@ -17,13 +21,59 @@ impl Handler for SolverWorker {
         debug!("Cols: {:?}", state.input.cols);
         debug!("Origin location: {:?}", state.input.origin_location);
 
-        debug!("Searching for solution ...");
         let board = state.board.as_deref().expect("Unable to access board");
+        let options = state.solve.expect("Unable to access the solve options");
+
+        let solver: Box<dyn Solver> = match options.solver {
+            // only the gf2 solver keeps track of every solution
+            _ if options.all_solutions => Box::new(Gf2Solver),
+            Some(kind) => solver::from_kind(kind),
+            None => solver::select(board).ok_or_else(|| {
+                state.command.error(
+                    ErrorKind::ArgumentConflict,
+                    "There is no solver able to handle this board",
+                )
+            })?,
+        };
+
+        if !solver.can_solve(board) {
+            return Err(state.command.error(
+                ErrorKind::ArgumentConflict,
+                format!(
+                    "The solver {} can't handle this {}x{} board",
+                    solver.name(),
+                    board.cols(),
+                    board.rows()
+                ),
+            ));
+        }
+
+        debug!("Solver: {}", solver.name());
+        debug!("Searching for solution ...");
+
+        if options.all_solutions {
+            let space = gf2::solution_space(board);
+            debug!("Rank: {}", space.rank);
+            debug!("Kernel dimension: {}", space.kernel_dimension());
+
+            state.solution = space.minimal(board);
+            state.solution_space = Some(space);
+        } else {
+            state.solution = solver.solve(board);
+        }
+        debug!("Final solution: {:?}", &state.solution);
 
-        let solution = gf2::solve(board);
-        debug!("Final solution: {:?}", &solution);
+        if state.solution.is_none() {
+            let diagnosis = if Gf2Solver.can_solve(board) {
+                gf2::explain_unsolvable(board)
+            } else {
+                warn!("Unable to explain why this board can't be solved");
+                vec![]
+            };
+            debug!("Quiet patterns: {:?}", diagnosis);
 
-        state.solution = solution;
+            state.diagnosis = Some(diagnosis);
+        }
 
         Ok(state)
     }
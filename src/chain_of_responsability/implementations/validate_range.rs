@@ -1,24 +1,151 @@
 use clap::error::ErrorKind;
 
 use crate::{
+    args::{Grid, PressOrder, Wrap},
     chain_of_responsability::{
-        chainable::Chainable, handler::Handler, state::State, worker::Worker,
+        chainable::Chainable, handler::Handler, implementations::sanitize_input::SanitizeWorker,
+        state::State, worker::Worker,
     },
     define_chainable,
+    solvers::neighbourhood::Neighbourhood,
 };
 
 define_chainable!(ValidateRangeWorker);
 
+impl ValidateRangeWorker {
+    /// Every index given in the arguments but the start
+    fn indices(state: &State) -> impl Iterator<Item = &usize> {
+        state
+            .input
+            .lights
+            .iter()
+            .chain(state.input.target.iter().flatten())
+            .chain(state.input.dont_care.iter())
+            .chain(state.input.forbid.iter())
+            .chain(state.input.allow.iter().flatten())
+            .chain(state.input.cost.iter().map(|(index, _)| index))
+            .chain(state.steps.iter())
+    }
+}
+
 impl Handler for ValidateRangeWorker {
     fn handle(&mut self, mut state: State) -> Result<State, clap::error::Error> {
         let rows = state.input.rows;
         let cols = state.input.cols;
-        let max_value = rows * cols;
+        let max_value = state.input.total_rows() * cols;
+        let size = if state.input.cube {
+            format!("{rows}x{cols}x6")
+        } else if state.input.layers > 1 {
+            format!("{rows}x{cols}x{}", state.input.layers)
+        } else {
+            format!("{rows}x{cols}")
+        };
+
+        let out_of_range = Self::indices(&state)
+            .chain(state.solve.iter().map(|options| &options.start))
+            .chain(
+                state
+                    .toggles
+                    .iter()
+                    .flatten()
+                    .flat_map(|(button, cells)| std::iter::once(button).chain(cells)),
+            )
+            .find(|&&it| it == 0 || it > max_value)
+            .copied();
+        if let Some(out_of_range) = out_of_range {
+            return Err(state.command.error(
+                ErrorKind::ArgumentConflict,
+                format!("Index {out_of_range} out of range for a {size} size"),
+            ));
+        }
+
+        // the holes start from the top left like the drawing of the shape, the start only
+        // matters when ordering by travel
+        let on_hole = Self::indices(&state)
+            .chain(
+                state
+                    .solve
+                    .iter()
+                    .filter(|options| options.order == PressOrder::Travel)
+                    .map(|options| &options.start),
+            )
+            .find(|&&index| {
+                let mut cell = [index - 1];
+                SanitizeWorker::rotate_light_indices(
+                    &mut cell,
+                    cols,
+                    state.input.rows,
+                    state.input.origin_location,
+                );
+                state.holes.contains(&cell[0])
+            })
+            .copied();
+        if let Some(on_hole) = on_hole {
+            return Err(state.command.error(
+                ErrorKind::ArgumentConflict,
+                format!("Index {on_hole} is a hole of the shape"),
+            ));
+        }
+
+        if let Some(costs) = &state.costs {
+            if costs.len() != max_value {
+                return Err(state.command.error(
+                    ErrorKind::InvalidValue,
+                    format!(
+                        "The cost file has {} costs but the board has {max_value} lights",
+                        costs.len()
+                    ),
+                ));
+            }
+        }
+
+        if !state.holes.is_empty() && state.toggles.is_some() {
+            return Err(state.command.error(
+                ErrorKind::ArgumentConflict,
+                "Boards with holes only work without a toggles file",
+            ));
+        }
+
+        if (state.toggles.is_some() || state.graph.is_some()) && state.input.states > 2 {
+            return Err(state.command.error(
+                ErrorKind::ArgumentConflict,
+                "Toggles and graph files only work with 2 states",
+            ));
+        }
+
+        if state.input.grid != Grid::Square
+            && (state.input.wrap != Wrap::None || state.input.pattern != Neighbourhood::Plus)
+        {
+            return Err(state.command.error(
+                ErrorKind::ArgumentConflict,
+                "Wrap and pattern only work with the square grid",
+            ));
+        }
+
+        // a press on a 3D board always toggles the cells sharing a face with it
+        if state.input.layers > 1
+            && (state.input.grid != Grid::Square
+                || state.input.wrap != Wrap::None
+                || state.input.pattern != Neighbourhood::Plus)
+        {
+            return Err(state.command.error(
+                ErrorKind::ArgumentConflict,
+                "Layers only work with the square grid, without wrap and with the plus pattern",
+            ));
+        }
+
+        let is_3d = state.input.layers > 1 || state.input.cube;
+        if is_3d && (state.input.states > 2 || state.toggles.is_some()) {
+            return Err(state.command.error(
+                ErrorKind::ArgumentConflict,
+                "3D boards only work with 2 states and without a toggles file",
+            ));
+        }
 
-        if let Some(out_of_range) = state.input.lights.iter().find(|&&it| it > max_value) {
+        if state.input.cube && rows != cols {
             return Err(state.command.error(
                 ErrorKind::ArgumentConflict,
-                format!("Index {out_of_range} out of range for a {rows}x{cols} size"),
+                "The faces of the cube must have the same number of rows and cols",
             ));
         }
 
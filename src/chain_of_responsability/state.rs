@@ -1,12 +1,36 @@
 use clap::{Command, CommandFactory};
 
-use crate::{args::Input, solvers::board::Board};
+use crate::{
+    args::{Cli, Input, SolveOptions},
+    solvers::{
+        board::Board,
+        gf2::{QuietPattern, SolutionSpace},
+        graph::Graph,
+    },
+};
 
 pub struct State {
     pub input: Input,
+    /// Options of the solve command, `None` for the rest of commands
+    pub solve: Option<SolveOptions>,
+    /// Presses to simulate on the board, in order
+    pub steps: Vec<usize>,
+    /// Board before the simulation and after each of its steps, only filled when tracing
+    pub trace: Vec<Box<dyn Board>>,
     pub board: Option<Box<dyn Board>>,
     pub command: Command,
     pub solution: Option<Vec<usize>>,
+    pub solution_space: Option<SolutionSpace>,
+    /// Set when the board has no solution, it holds the quiet patterns that explain why
+    pub diagnosis: Option<Vec<QuietPattern>>,
+    /// Buttons read from the toggles file with the cells they toggle
+    pub toggles: Option<Vec<(usize, Vec<usize>)>>,
+    /// Graph read from the graph file, without any light on
+    pub graph: Option<Graph>,
+    /// Cells missing from the shape file, starting from the top left
+    pub holes: Vec<usize>,
+    /// Costs read from the cost file, starting from the top left
+    pub costs: Option<Vec<usize>>,
 }
 
 impl State {
@@ -14,9 +38,30 @@ impl State {
     pub fn new(input: Input) -> Self {
         Self {
             input,
+            solve: None,
+            steps: vec![],
+            trace: vec![],
             board: None,
-            command: Input::command(),
+            command: Cli::command(),
             solution: None,
+            solution_space: None,
+            diagnosis: None,
+            toggles: None,
+            graph: None,
+            holes: vec![],
+            costs: None,
         }
     }
+
+    #[must_use]
+    pub fn with_solve(mut self, options: SolveOptions) -> Self {
+        self.solve = Some(options);
+        self
+    }
+
+    #[must_use]
+    pub fn with_steps(mut self, steps: Vec<usize>) -> Self {
+        self.steps = steps;
+        self
+    }
 }
@@ -4,9 +4,13 @@ pub mod solvers;
 
 #[cfg(test)]
 mod args_tests {
+    use crate::args::Action;
+    use crate::args::Cli;
     use crate::args::Display;
-    use crate::args::Input;
     use crate::args::Origin;
+    use crate::args::SolverKind;
+    use crate::args::Wrap;
+    use crate::solvers::neighbourhood::Neighbourhood;
     use clap::CommandFactory;
     use clap::Parser;
 
@@ -18,32 +22,212 @@ mod args_tests {
 
     #[test]
     fn verify_clap() {
-        Input::command().debug_assert();
+        Cli::command().debug_assert();
     }
 
     #[test]
     fn test_name() {
-        assert_eq!(Input::command().get_name(), "Lights Out Puzzle Solver");
+        assert_eq!(Cli::command().get_name(), "Lights Out Puzzle Solver");
     }
 
     #[test]
     fn test_input_lights() {
-        let input = Input::try_parse_from(test_args!("7", "9", "1", "3"))
-            .expect("ligths are not parsed properly");
+        let solve = Cli::try_parse_from(test_args!("7", "9", "1", "3"))
+            .expect("ligths are not parsed properly")
+            .solve;
 
-        assert_eq!(input.lights, vec![7, 9, 1, 3]);
+        assert_eq!(solve.input.lights, vec![7, 9, 1, 3]);
+    }
+
+    #[test]
+    fn test_input_target() {
+        let solve = Cli::try_parse_from(test_args!("1", "--target", "2,3", "4"))
+            .expect("target is not parsed properly")
+            .solve;
+        assert_eq!(solve.input.target, Some(vec![2, 3]));
+        assert_eq!(solve.input.lights, vec![1, 4]);
+
+        let solve = Cli::try_parse_from(test_args!("-t", "2", "-t", "3"))
+            .expect("repeated target is not parsed properly")
+            .solve;
+        assert_eq!(solve.input.target, Some(vec![2, 3]));
+
+        let solve = Cli::try_parse_from(test_args!("1", "--target"))
+            .expect("empty target is not parsed properly")
+            .solve;
+        assert_eq!(solve.input.target, Some(vec![]));
+    }
+
+    #[test]
+    fn test_input_dont_care() {
+        let solve = Cli::try_parse_from(test_args!("-x", "1,2", "3", "-x", "4"))
+            .expect("don't care is not parsed properly")
+            .solve;
+        assert_eq!(solve.input.dont_care, vec![1, 2, 4]);
+        assert_eq!(solve.input.lights, vec![3]);
+    }
+
+    #[test]
+    fn test_input_forbid() {
+        let solve = Cli::try_parse_from(test_args!("--forbid", "5", "1", "3", "--forbid", "7,9"))
+            .expect("forbid is not parsed properly")
+            .solve;
+        assert_eq!(solve.input.forbid, vec![5, 7, 9]);
+        assert_eq!(solve.input.lights, vec![1, 3]);
+
+        let solve = Cli::try_parse_from(test_args!("--allow", "2,4", "1"))
+            .expect("allow is not parsed properly")
+            .solve;
+        assert_eq!(solve.input.allow, Some(vec![2, 4]));
+        assert_eq!(solve.input.lights, vec![1]);
+    }
+
+    #[test]
+    fn test_input_cost() {
+        let solve =
+            Cli::try_parse_from(test_args!("--cost", "1=10", "1", "6", "--cost", "2=3,4=5"))
+                .expect("cost is not parsed properly")
+                .solve;
+        assert_eq!(solve.input.cost, vec![(1, 10), (2, 3), (4, 5)]);
+        assert_eq!(solve.input.lights, vec![1, 6]);
+
+        assert!(Cli::try_parse_from(test_args!("--cost", "1")).is_err());
+    }
+
+    #[test]
+    fn test_input_size() {
+        assert!(Cli::try_parse_from(test_args!("-c", "0")).is_err());
+        assert!(Cli::try_parse_from(test_args!("analyze", "-r", "0")).is_err());
+        assert!(Cli::try_parse_from(test_args!("generate", "-c", "0")).is_err());
+
+        let solve = Cli::try_parse_from(test_args!("-c", "1", "-r", "5"))
+            .expect("size is not parsed properly")
+            .solve;
+        assert_eq!((solve.input.cols, solve.input.rows), (1, 5));
+    }
+
+    #[test]
+    fn test_input_states() {
+        assert!(Cli::try_parse_from(test_args!("--states", "1")).is_err());
+
+        let solve = Cli::try_parse_from(test_args!("--states", "3"))
+            .expect("states are not parsed properly")
+            .solve;
+        assert_eq!(solve.input.states, 3);
+    }
+
+    #[test]
+    fn test_input_solver() {
+        let solve = Cli::try_parse_from(test_args!("--solver", "recursive"))
+            .expect("solver is not parsed properly")
+            .solve;
+
+        assert_eq!(solve.options.solver, Some(SolverKind::Recursive));
+    }
+
+    #[test]
+    fn test_all_solutions_conflicts_solver() {
+        let input = Cli::try_parse_from(test_args!("--all-solutions", "--solver", "chase"));
+
+        assert!(input.is_err());
     }
 
     #[test]
     fn test_defaults() {
-        let input = Input::try_parse_from(test_args!()).expect("ligths are not parsed properly");
-
-        assert_eq!(input.lights.len(), 0);
-        assert_eq!(input.cols, 3);
-        assert_eq!(input.rows, 3);
-        assert!(!input.verbose);
-        assert_eq!(input.simulation_steps.len(), 0);
-        assert_eq!(input.display_mode, Display::Draw);
-        assert_eq!(input.origin_location, Origin::BottomLeft);
+        let solve = Cli::try_parse_from(test_args!())
+            .expect("ligths are not parsed properly")
+            .solve;
+
+        assert_eq!(solve.input.lights.len(), 0);
+        assert_eq!(solve.input.cols, 3);
+        assert_eq!(solve.input.rows, 3);
+        assert!(!solve.input.verbose);
+        assert_eq!(solve.simulation_steps.len(), 0);
+        assert_eq!(solve.input.display_mode, Display::Draw);
+        assert_eq!(solve.input.origin_location, Origin::BottomLeft);
+        assert_eq!(solve.options.solver, None);
+        assert!(!solve.options.all_solutions);
+        assert_eq!(solve.input.target, None);
+        assert_eq!(solve.input.states, 2);
+        assert_eq!(solve.input.wrap, Wrap::None);
+        assert_eq!(solve.input.pattern, Neighbourhood::Plus);
+    }
+
+    #[test]
+    fn test_flat_form_is_solve() {
+        let action = Cli::try_parse_from(test_args!("7", "9", "--solver", "gf2"))
+            .expect("flat form is not parsed properly")
+            .into_action();
+        let Action::Solve(solve) = action else {
+            panic!("flat form is not solve");
+        };
+        assert_eq!(solve.input.lights, vec![7, 9]);
+        assert_eq!(solve.options.solver, Some(SolverKind::Gf2));
+
+        let action = Cli::try_parse_from(test_args!("solve", "7", "9"))
+            .expect("solve is not parsed properly")
+            .into_action();
+        assert!(matches!(action, Action::Solve(solve) if solve.input.lights == vec![7, 9]));
+
+        // the steps take every value after them like in simulate
+        let action = Cli::try_parse_from(test_args!("5", "-s", "1", "2", "3"))
+            .expect("the steps are not parsed properly")
+            .into_action();
+        assert!(
+            matches!(action, Action::Solve(solve) if solve.input.lights == vec![5] && solve.simulation_steps == vec![1, 2, 3])
+        );
+    }
+
+    #[test]
+    fn test_subcommands() {
+        let action = Cli::try_parse_from(test_args!("simulate", "1", "-s", "2", "3"))
+            .expect("simulate is not parsed properly")
+            .into_action();
+        assert!(
+            matches!(action, Action::Simulate(simulate) if simulate.simulation_steps == vec![2, 3])
+        );
+        assert!(Cli::try_parse_from(test_args!("simulate", "1")).is_err());
+
+        let action = Cli::try_parse_from(test_args!("generate", "-c", "4", "--seed", "7"))
+            .expect("generate is not parsed properly")
+            .into_action();
+        assert!(
+            matches!(action, Action::Generate(generate) if generate.seed == Some(7) && generate.input.cols == 4)
+        );
+
+        let action = Cli::try_parse_from(test_args!("analyze", "1", "2"))
+            .expect("analyze is not parsed properly")
+            .into_action();
+        assert!(matches!(action, Action::Analyze(input) if input.lights == vec![1, 2]));
+
+        let action = Cli::try_parse_from(test_args!("play", "5"))
+            .expect("play is not parsed properly")
+            .into_action();
+        assert!(matches!(action, Action::Play(input) if input.lights == vec![5]));
+
+        // the options of solving belong only to solve
+        assert!(Cli::try_parse_from(test_args!("analyze", "--solver", "gf2")).is_err());
+        assert!(Cli::try_parse_from(test_args!("--solver", "gf2", "analyze")).is_err());
+    }
+
+    #[test]
+    fn test_trace_requires_steps() {
+        assert!(Cli::try_parse_from(test_args!("--trace")).is_err());
+
+        let solve = Cli::try_parse_from(test_args!("-s", "1", "--trace"))
+            .expect("trace is not parsed properly")
+            .solve;
+        assert!(solve.trace);
+    }
+
+    #[test]
+    fn test_then_solve_requires_steps() {
+        assert!(Cli::try_parse_from(test_args!("--then-solve")).is_err());
+
+        let solve = Cli::try_parse_from(test_args!("2", "3", "-s", "1", "--then-solve"))
+            .expect("then solve is not parsed properly")
+            .solve;
+        assert!(solve.then_solve);
+        assert_eq!(solve.simulation_steps, vec![1]);
     }
 }
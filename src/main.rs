@@ -1,11 +1,12 @@
 use clap::Parser;
 use lights_out_solver::{
-    args::Input,
+    args::{Action, Cli, Input},
     chain_of_responsability::{
         chainable::Chainable,
         implementations::{
-            print::PrintWorker, sanitize_input::SanitizeWorker, simulator::SimulatorWorker,
-            solver::SolverWorker, validate_range::ValidateRangeWorker,
+            analyze::AnalyzeWorker, generate::GenerateWorker, load::LoadWorker, order::OrderWorker,
+            play::PlayWorker, print::PrintWorker, sanitize_input::SanitizeWorker,
+            simulator::SimulatorWorker, solver::SolverWorker, validate_range::ValidateRangeWorker,
         },
         state::State,
         worker::Worker,
@@ -15,34 +16,86 @@ use log::info;
 
 use simple_logger::SimpleLogger;
 
+/// Exit code used when the board has no solution
+const UNSOLVABLE_EXIT_CODE: i32 = 3;
+
 fn main() {
-    let input = Input::parse();
-    set_up_logger(&input);
+    let action = Cli::parse().into_action();
+    set_up_logger(action.input());
 
-    let mut worker = get_worker_chain(&input);
-    let state = State::new(input);
+    let (mut worker, state) = get_worker_chain(action);
 
-    if let Some(err) = worker.execute(state).err() {
-        err.exit();
+    match worker.execute(state) {
+        Ok(state) if state.diagnosis.is_some() => std::process::exit(UNSOLVABLE_EXIT_CODE),
+        Ok(_) => {}
+        Err(err) => err.exit(),
     }
 }
 
-fn get_worker_chain(input: &Input) -> Box<dyn Worker> {
-    let mut validator = Box::<ValidateRangeWorker>::default();
+/// Every command loads, validates and builds the board before running its own workers
+fn get_worker_chain(action: Action) -> (Box<dyn Worker>, State) {
+    let mut loader = Box::<LoadWorker>::default();
+    let validator = Box::<ValidateRangeWorker>::default();
     let sanitizer = Box::<SanitizeWorker>::default();
 
-    let sanitizer = validator.set_next(sanitizer);
+    let sanitizer = loader.set_next(validator).set_next(sanitizer);
 
-    if input.simulation_steps.is_empty() {
-        let solver = Box::<SolverWorker>::default();
-        let printer = Box::<PrintWorker>::default();
-        sanitizer.set_next(solver).set_next(printer);
-    } else {
-        let simulator = Box::<SimulatorWorker>::default();
-        sanitizer.set_next(simulator);
-    }
+    let state = match action {
+        Action::Solve(solve) => {
+            if solve.simulation_steps.is_empty() {
+                let solver = Box::<SolverWorker>::default();
+                let order = Box::<OrderWorker>::default();
+                let printer = Box::<PrintWorker>::default();
+                sanitizer.set_next(solver).set_next(order).set_next(printer);
+            } else if solve.then_solve {
+                let simulator = Box::new(SimulatorWorker::new(solve.trace));
+                let solver = Box::<SolverWorker>::default();
+                let order = Box::<OrderWorker>::default();
+                let printer = Box::<PrintWorker>::default();
+                sanitizer
+                    .set_next(simulator)
+                    .set_next(solver)
+                    .set_next(order)
+                    .set_next(printer);
+            } else {
+                let simulator = Box::new(SimulatorWorker::new(solve.trace));
+                let printer = Box::<PrintWorker>::default();
+                sanitizer.set_next(simulator).set_next(printer);
+            }
+
+            State::new(solve.input)
+                .with_solve(solve.options)
+                .with_steps(solve.simulation_steps)
+        }
+        Action::Simulate(simulate) => {
+            let simulator = Box::new(SimulatorWorker::new(simulate.trace));
+            let printer = Box::<PrintWorker>::default();
+            sanitizer.set_next(simulator).set_next(printer);
+
+            State::new(simulate.input).with_steps(simulate.simulation_steps)
+        }
+        Action::Generate(generate) => {
+            let generator = Box::new(GenerateWorker::new(generate.presses, generate.seed));
+            let printer = Box::<PrintWorker>::default();
+            sanitizer.set_next(generator).set_next(printer);
+
+            State::new(generate.input)
+        }
+        Action::Analyze(input) => {
+            let analyzer = Box::<AnalyzeWorker>::default();
+            sanitizer.set_next(analyzer);
+
+            State::new(input)
+        }
+        Action::Play(input) => {
+            let player = Box::<PlayWorker>::default();
+            sanitizer.set_next(player);
+
+            State::new(input)
+        }
+    };
 
-    validator
+    (loader, state)
 }
 
 fn set_up_logger(input: &Input) {
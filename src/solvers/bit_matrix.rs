@@ -0,0 +1,157 @@
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// Vector over GF(2) packed in 64 bit words
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitVector {
+    len: usize,
+    words: Vec<u64>,
+}
+
+impl BitVector {
+    #[must_use]
+    pub fn new(len: usize) -> BitVector {
+        BitVector {
+            len,
+            words: vec![0u64; len.div_ceil(WORD_BITS)],
+        }
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[must_use]
+    pub fn get(&self, index: usize) -> bool {
+        self.words[index / WORD_BITS] & (1 << (index % WORD_BITS)) != 0
+    }
+
+    pub fn set(&mut self, index: usize, value: bool) {
+        let mask = 1 << (index % WORD_BITS);
+        if value {
+            self.words[index / WORD_BITS] |= mask;
+        } else {
+            self.words[index / WORD_BITS] &= !mask;
+        }
+    }
+
+    pub fn toggle(&mut self, index: usize) {
+        self.words[index / WORD_BITS] ^= 1 << (index % WORD_BITS);
+    }
+
+    pub fn swap(&mut self, i: usize, j: usize) {
+        let (a, b) = (self.get(i), self.get(j));
+        self.set(i, b);
+        self.set(j, a);
+    }
+
+    /// Adds `other` to this vector, skipping the bits before `from` that are known to be 0
+    pub fn xor_from(&mut self, other: &BitVector, from: usize) {
+        self.words[from / WORD_BITS..]
+            .iter_mut()
+            .zip(other.words[from / WORD_BITS..].iter())
+            .for_each(|(a, b)| *a ^= b);
+    }
+
+    pub fn xor(&mut self, other: &BitVector) {
+        self.xor_from(other, 0);
+    }
+
+    #[must_use]
+    pub fn count_ones(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    /// Scalar product, true when both vectors share an odd number of ones
+    #[must_use]
+    pub fn dot(&self, other: &BitVector) -> bool {
+        self.words
+            .iter()
+            .zip(other.words.iter())
+            .fold(0, |acc, (a, b)| acc ^ (a & b).count_ones())
+            % 2
+            != 0
+    }
+
+    pub fn iter_ones(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(index, &word)| {
+            let mut word = word;
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+                let bit = word.trailing_zeros() as usize;
+                word &= word - 1;
+                Some(index * WORD_BITS + bit)
+            })
+        })
+    }
+}
+
+/// Row major matrix over GF(2), every row is a [`BitVector`] so row operations are word wise XORs
+#[derive(Debug, Clone)]
+pub struct BitMatrix {
+    cols: usize,
+    rows: Vec<BitVector>,
+}
+
+impl BitMatrix {
+    #[must_use]
+    pub fn new(rows: usize, cols: usize) -> BitMatrix {
+        BitMatrix {
+            cols,
+            rows: vec![BitVector::new(cols); rows],
+        }
+    }
+
+    #[must_use]
+    pub fn rows(&self) -> usize {
+        self.rows.len()
+    }
+
+    #[must_use]
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    #[must_use]
+    pub fn get(&self, row: usize, col: usize) -> bool {
+        self.rows[row].get(col)
+    }
+
+    pub fn set(&mut self, row: usize, col: usize, value: bool) {
+        self.rows[row].set(col, value);
+    }
+
+    #[must_use]
+    pub fn row(&self, row: usize) -> &BitVector {
+        &self.rows[row]
+    }
+
+    pub fn swap_rows(&mut self, i: usize, j: usize) {
+        self.rows.swap(i, j);
+    }
+
+    /// Adds the row `source` to the row `target` starting at the column `from`
+    pub fn xor_rows(&mut self, target: usize, source: usize, from: usize) {
+        assert!(target != source, "trying to add row to itself");
+
+        let (target, source) = if target < source {
+            let (head, tail) = self.rows.split_at_mut(source);
+            (&mut head[target], &tail[0])
+        } else {
+            let (head, tail) = self.rows.split_at_mut(target);
+            (&mut tail[0], &head[source])
+        };
+
+        target.xor_from(source, from);
+    }
+}
@@ -1,22 +1,157 @@
 // Credit https://github.com/oovm/deus-rs/blob/master/src/solvers/state2.rs
 
+use crate::args::{Grid, Wrap};
+
+use super::{grid, neighbourhood::Neighbourhood};
+
 pub trait Board {
     fn size(&self) -> (usize, usize);
     fn cols(&self) -> usize;
     fn rows(&self) -> usize;
-    fn is_solved(&self) -> bool;
     fn trigger_coord(&mut self, col: usize, row: usize) -> &mut dyn Board;
     fn trigger_index(&mut self, index: usize) -> &mut dyn Board;
     fn get(&self, col: usize, row: usize) -> Option<usize>;
     fn set(&mut self, col: usize, row: usize, value: usize) -> bool;
     fn iter(&self) -> std::slice::Iter<'_, usize>;
+    /// Indices of the cells that change when pressing the cell at `index`
+    fn toggled_cells(&self, index: usize) -> Vec<usize>;
+    /// Goal of the board, the cells that can be pressed and what each press costs
+    fn rules(&self) -> &Rules;
+    fn rules_mut(&mut self) -> &mut Rules;
+    /// Number of values each cell cycles through, 2 for the classic on/off lights
+    fn states(&self) -> usize;
+    fn boxed_clone(&self) -> Box<dyn Board>;
+
+    /// Cells missing from the shape of the board, they are never lit nor pressed
+    fn is_hole(&self, _index: usize) -> bool {
+        false
+    }
+
+    /// Value the cell at `index` must have once the board is solved
+    fn target(&self, index: usize) -> usize {
+        self.rules().target[index]
+    }
+
+    /// Cells whose final value doesn't matter to solve the board
+    fn is_masked(&self, index: usize) -> bool {
+        self.rules().mask[index] || self.is_hole(index)
+    }
+
+    /// Cells that can be pressed to solve the board
+    fn is_pressable(&self, index: usize) -> bool {
+        !self.is_hole(index) && !self.rules().forbidden[index]
+    }
+
+    fn is_solved(&self) -> bool {
+        self.iter()
+            .enumerate()
+            .all(|(index, &value)| self.is_masked(index) || value == self.target(index))
+    }
+
+    /// Cost of pressing the cell at `index`, the best solution is the one with the lowest total
+    fn cost(&self, index: usize) -> usize {
+        self.rules().costs[index]
+    }
+
+    /// Lays out one string per cell the way the board looks, by default a line per row
+    fn draw(&self, cells: &[String]) -> String {
+        grid::draw(Grid::Square, self.cols(), cells)
+    }
+
+    /// Sets the goal of the board to only have on the lights at `active`, by default all of them
+    /// are on. With more states every time a position shows up its value increases by one
+    #[must_use]
+    fn with_target(mut self, active: &[usize]) -> Self
+    where
+        Self: Sized,
+    {
+        let states = self.states();
+        let target = &mut self.rules_mut().target;
+        target.fill(0);
+        active
+            .iter()
+            .for_each(|&position| target[position] = (target[position] + 1) % states);
+
+        self
+    }
+
+    /// Marks the cells at `positions` as don't care, they can end up with any value
+    #[must_use]
+    fn with_mask(mut self, positions: &[usize]) -> Self
+    where
+        Self: Sized,
+    {
+        let mask = &mut self.rules_mut().mask;
+        positions.iter().for_each(|&position| mask[position] = true);
+
+        self
+    }
+
+    /// Forbids pressing the cells at `positions`, the solution can only use the rest
+    #[must_use]
+    fn with_forbidden(mut self, positions: &[usize]) -> Self
+    where
+        Self: Sized,
+    {
+        let forbidden = &mut self.rules_mut().forbidden;
+        positions
+            .iter()
+            .for_each(|&position| forbidden[position] = true);
+
+        self
+    }
+
+    /// Sets the cost of pressing each cell, by default all of them cost 1
+    #[must_use]
+    fn with_costs(mut self, costs: &[usize]) -> Self
+    where
+        Self: Sized,
+    {
+        self.rules_mut()
+            .costs
+            .iter_mut()
+            .zip(costs.iter())
+            .for_each(|(c, &cost)| *c = cost);
+
+        self
+    }
 }
 
-#[derive(Debug)]
+/// What solves a board and how it can be played, every board keeps one
+#[derive(Debug, Clone)]
+pub struct Rules {
+    target: Vec<usize>,
+    mask: Vec<bool>,
+    forbidden: Vec<bool>,
+    costs: Vec<usize>,
+}
+
+impl Rules {
+    /// Every light has to end up on and every cell can be pressed with a cost of 1
+    #[must_use]
+    pub fn new(cells: usize) -> Rules {
+        Rules {
+            target: vec![1usize; cells],
+            mask: vec![false; cells],
+            forbidden: vec![false; cells],
+            costs: vec![1; cells],
+        }
+    }
+}
+
+/// Board on a grid where every press moves the cells to the next of `states` values, with the
+/// default 2 states they are the classic on/off lights
+#[derive(Debug, Clone)]
 pub struct Binary {
     cols: usize,
     rows: usize,
+    states: usize,
     board: Vec<usize>,
+    rules: Rules,
+    holes: Vec<bool>,
+    wrap: Wrap,
+    neighbourhood: Neighbourhood,
+    grid: Grid,
 }
 
 impl Binary {
@@ -25,29 +160,102 @@ impl Binary {
         Binary {
             cols,
             rows,
+            states: 2,
             board: vec![0usize; cols * rows],
+            rules: Rules::new(cols * rows),
+            holes: vec![false; cols * rows],
+            wrap: Wrap::None,
+            neighbourhood: Neighbourhood::Plus,
+            grid: Grid::Square,
         }
     }
 
     #[must_use]
     pub fn new_from_positions(active: &[usize], cols: usize, rows: usize) -> Binary {
-        let mut board = vec![0usize; cols * rows];
+        let mut binary = Binary::new_blank(cols, rows);
 
-        active.iter().for_each(|position| board[*position] = 1);
+        active
+            .iter()
+            .for_each(|position| binary.board[*position] = 1);
 
-        Binary { cols, rows, board }
+        binary
     }
 
     #[must_use]
     pub fn new_from_values(active: &[bool], cols: usize, rows: usize) -> Binary {
-        let mut board = vec![0usize; cols * rows];
+        let mut binary = Binary::new_blank(cols, rows);
 
-        board
+        binary
+            .board
             .iter_mut()
             .zip(active.iter())
             .for_each(|(b, &a)| *b = usize::from(a));
 
-        Binary { cols, rows, board }
+        binary
+    }
+
+    /// Number of values each cell cycles through like in Lights Out 2000, the values already
+    /// set are taken modulo `states`
+    #[must_use]
+    pub fn with_states(mut self, states: usize) -> Binary {
+        assert!(states >= 2, "a board needs at least 2 states");
+
+        self.states = states;
+        self.board.iter_mut().for_each(|value| *value %= states);
+        self.rules
+            .target
+            .iter_mut()
+            .for_each(|value| *value %= states);
+
+        self
+    }
+
+    /// Sets the value of every cell, taken modulo the number of states
+    #[must_use]
+    pub fn with_values(mut self, values: &[usize]) -> Binary {
+        self.board
+            .iter_mut()
+            .zip(values.iter())
+            .for_each(|(b, &v)| *b = v % self.states);
+
+        self
+    }
+
+    /// Removes the cells at `positions` from the board, they are never lit, can't be pressed
+    /// and pressing their neighbours doesn't change them
+    #[must_use]
+    pub fn with_holes(mut self, positions: &[usize]) -> Binary {
+        positions.iter().for_each(|&position| {
+            self.holes[position] = true;
+            self.board[position] = 0;
+        });
+
+        self
+    }
+
+    /// Connects the edges of the board so presses wrap around them
+    #[must_use]
+    pub fn with_wrap(mut self, wrap: Wrap) -> Binary {
+        self.wrap = wrap;
+
+        self
+    }
+
+    /// Changes the cells toggled by each press, by default the plus shaped one
+    #[must_use]
+    pub fn with_neighbourhood(mut self, neighbourhood: Neighbourhood) -> Binary {
+        self.neighbourhood = neighbourhood;
+
+        self
+    }
+
+    /// Changes the shape of the cells, on hex and triangle grids a press changes the cell and
+    /// the ones sharing an edge with it and the neighbourhood is ignored
+    #[must_use]
+    pub fn with_grid(mut self, grid: Grid) -> Binary {
+        self.grid = grid;
+
+        self
     }
 
     fn get_index(&self, col: usize, row: usize) -> usize {
@@ -82,51 +290,72 @@ impl Board for Binary {
     }
 
     fn set(&mut self, col: usize, row: usize, value: usize) -> bool {
-        if col < self.cols && row < self.rows {
-            match value {
-                0..=1 => {
-                    let index = self.get_index(col, row);
-                    self.board[index] = value;
-                    true
-                }
-                _ => false,
-            }
+        if col < self.cols && row < self.rows && value < self.states {
+            let index = self.get_index(col, row);
+            self.board[index] = value;
+            true
         } else {
             false
         }
     }
 
-    fn is_solved(&self) -> bool {
-        self.board.iter().all(|val| *val == 1)
+    fn rules(&self) -> &Rules {
+        &self.rules
     }
 
-    fn trigger_index(&mut self, index: usize) -> &mut dyn Board {
-        let col = index % self.cols;
-        let row = index / self.cols;
+    fn rules_mut(&mut self) -> &mut Rules {
+        &mut self.rules
+    }
 
-        self.trigger_coord(col, row)
+    fn is_hole(&self, index: usize) -> bool {
+        self.holes[index]
     }
 
-    fn trigger_coord(&mut self, col: usize, row: usize) -> &mut dyn Board {
-        fn switch(this: &mut Binary, col: usize, row: usize) {
-            match this.get(col, row) {
-                Some(1) => this.set(col, row, 0),
-                Some(0) => this.set(col, row, 1),
-                _ => false,
-            };
+    fn states(&self) -> usize {
+        self.states
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Board> {
+        Box::new(self.clone())
+    }
+
+    fn trigger_index(&mut self, index: usize) -> &mut dyn Board {
+        if index >= self.board.len() {
+            return self;
         }
+        for cell in self.toggled_cells(index) {
+            self.board[cell] = (self.board[cell] + 1) % self.states;
+        }
+        self
+    }
+
+    fn trigger_coord(&mut self, col: usize, row: usize) -> &mut dyn Board {
         if col >= self.cols || row >= self.rows {
             return self;
         }
-        if row > 0 {
-            switch(self, col, row - 1);
-        }
-        if col > 0 {
-            switch(self, col - 1, row);
+        let index = self.get_index(col, row);
+        self.trigger_index(index)
+    }
+
+    fn toggled_cells(&self, index: usize) -> Vec<usize> {
+        if self.holes[index] {
+            return vec![];
         }
-        switch(self, col, row);
-        switch(self, col + 1, row);
-        switch(self, col, row + 1);
-        self
+
+        let cells = match self.grid {
+            Grid::Square => self
+                .neighbourhood
+                .cells(self.cols, self.rows, self.wrap, index),
+            Grid::Hex => grid::hex_neighbours(self.cols, self.rows, index),
+            Grid::Triangle => grid::triangle_neighbours(self.cols, self.rows, index),
+        };
+        cells
+            .into_iter()
+            .filter(|&cell| !self.holes[cell])
+            .collect()
+    }
+
+    fn draw(&self, cells: &[String]) -> String {
+        grid::draw(self.grid, self.cols, cells)
     }
 }
@@ -0,0 +1,183 @@
+use super::{
+    bit_matrix::{BitMatrix, BitVector},
+    board::Board,
+    gf2,
+    solver::{Capabilities, Solver},
+};
+
+/// Light chasing, its cost grows with the area of the board
+pub struct ChaseSolver;
+
+impl Solver for ChaseSolver {
+    fn name(&self) -> &'static str {
+        "chase"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            minimal: true,
+            max_cells: None,
+            masks: false,
+            multi_state: false,
+            neighbourhoods: false,
+            restricted: false,
+            weighted: true,
+        }
+    }
+
+    fn solve(&self, board: &dyn Board) -> Option<Vec<usize>> {
+        solve(board)
+    }
+}
+
+/// Solves the board using light chasing, once the first row is known pressing below
+/// every wrong light leaves only the last row to fix. The presses of the first row come
+/// from a `cols`x`cols` system so the cost grows with the area of the board
+pub fn solve(board: &dyn Board) -> Option<Vec<usize>> {
+    let (cols, rows) = board.size();
+    if cols == 0 || rows == 0 {
+        return Some(vec![]);
+    }
+
+    // chase with the first rows as unknowns, the leftovers tell which presses are valid
+    let unknowns = seed_rows(board) * cols;
+    let seeds = (0..unknowns)
+        .map(|var| {
+            let mut form = BitVector::new(unknowns + 1);
+            form.set(var, true);
+            form
+        })
+        .collect::<Vec<_>>();
+    let residual = chase(board, seeds, false, |_, _| {});
+
+    let mut matrix = BitMatrix::new(residual.len(), unknowns);
+    let mut expected = BitVector::new(residual.len());
+    for (row, form) in residual.iter().enumerate() {
+        for var in form.iter_ones() {
+            if var < unknowns {
+                matrix.set(row, var, true);
+            } else {
+                expected.set(row, true);
+            }
+        }
+    }
+
+    let space = gf2::solve_system(matrix, expected);
+    let particular = space.particular?;
+
+    // chase again with the known first rows to get the presses of the whole board
+    let expand = |first_rows: &BitVector, homogeneous: bool| {
+        let mut presses = BitVector::new(cols * rows);
+        let seeds = (0..unknowns)
+            .map(|var| {
+                let mut form = BitVector::new(1);
+                form.set(0, first_rows.get(var));
+                form
+            })
+            .collect();
+        chase(board, seeds, homogeneous, |index, form| {
+            presses.set(index, form.get(0));
+        });
+        presses
+    };
+
+    let particular = expand(&particular, false);
+    let kernel = space
+        .kernel
+        .iter()
+        .map(|vector| expand(vector, true))
+        .collect::<Vec<_>>();
+
+    Some(
+        gf2::minimise(particular, &kernel, board)
+            .iter_ones()
+            .collect(),
+    )
+}
+
+/// When the top and bottom edges are connected the first row also depends on the last one,
+/// chasing from the first two rows leaves both the first and the last row to fix at the end
+fn seed_rows(board: &dyn Board) -> usize {
+    let (cols, rows) = board.size();
+    let wraps = rows > 2
+        && board
+            .toggled_cells(0)
+            .iter()
+            .any(|&cell| cell / cols == rows - 1);
+
+    if wraps {
+        2
+    } else {
+        1
+    }
+}
+
+/// Presses below every wrong light row by row. Each press is an affine form over the unknowns
+/// of the seed rows, the last bit of the form is the constant term.
+/// `homogeneous` chases a blank board that has to stay blank, used to expand the quiet patterns.
+///
+/// Every press is reported to `record` and the forms left in the rows that can't be chased are
+/// returned, the board is solved when all of them are 0
+fn chase(
+    board: &dyn Board,
+    seeds: Vec<BitVector>,
+    homogeneous: bool,
+    mut record: impl FnMut(usize, &BitVector),
+) -> Vec<BitVector> {
+    let (cols, rows) = board.size();
+    let len = seeds[0].len();
+    let constant = len - 1;
+    let seed_rows = seeds.len() / cols;
+
+    // presses of each row, besides the seeds only the last two rows are kept
+    let mut presses: Vec<Option<Vec<BitVector>>> = vec![None; rows];
+    for (row, forms) in seeds.chunks(cols).enumerate() {
+        forms
+            .iter()
+            .enumerate()
+            .for_each(|(col, form)| record(row * cols + col, form));
+        presses[row] = Some(forms.to_vec());
+    }
+
+    // what is left to fix in each cell of `row` with the presses known so far
+    let leftover = |row: usize, presses: &[Option<Vec<BitVector>>]| {
+        (0..cols)
+            .map(|col| {
+                let cell = row * cols + col;
+                let mut form = BitVector::new(len);
+
+                if !homogeneous && board.get(col, row) != Some(board.target(cell)) {
+                    form.set(constant, true);
+                }
+
+                // the board is symmetric so the presses that toggle a cell are its own neighbours
+                for press in board.toggled_cells(cell) {
+                    if let Some(forms) = &presses[press / cols] {
+                        form.xor(&forms[press % cols]);
+                    }
+                }
+
+                form
+            })
+            .collect::<Vec<_>>()
+    };
+
+    for row in seed_rows - 1..rows - 1 {
+        let below = leftover(row, &presses);
+        below
+            .iter()
+            .enumerate()
+            .for_each(|(col, form)| record((row + 1) * cols + col, form));
+
+        presses[row + 1] = Some(below);
+        if row > seed_rows {
+            presses[row - 1] = None;
+        }
+    }
+
+    let mut residual = leftover(rows - 1, &presses);
+    if seed_rows > 1 {
+        residual.extend(leftover(0, &presses));
+    }
+    residual
+}
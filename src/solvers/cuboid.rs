@@ -0,0 +1,222 @@
+use std::collections::HashMap;
+
+use crate::args::Grid;
+
+use super::{
+    board::{Board, Rules},
+    grid,
+};
+
+/// Board in 3 dimensions where a press toggles the cell and the ones sharing a face with it.
+/// The layers are stacked one below the other, so `row` goes through every layer
+#[derive(Debug, Clone)]
+pub struct Cuboid {
+    cols: usize,
+    rows: usize,
+    layers: usize,
+    board: Vec<usize>,
+    rules: Rules,
+    toggles: Vec<Vec<usize>>,
+}
+
+impl Cuboid {
+    /// Solid block of `cols` x `rows` x `layers` cells
+    #[must_use]
+    pub fn new_blank(cols: usize, rows: usize, layers: usize) -> Cuboid {
+        let layer = cols * rows;
+        let toggles = (0..layer * layers)
+            .map(|index| {
+                let col = index % cols;
+                let row = index % layer / cols;
+                let depth = index / layer;
+                let mut cells = vec![index];
+
+                if depth > 0 {
+                    cells.push(index - layer);
+                }
+                if row > 0 {
+                    cells.push(index - cols);
+                }
+                if col > 0 {
+                    cells.push(index - 1);
+                }
+                if col + 1 < cols {
+                    cells.push(index + 1);
+                }
+                if row + 1 < rows {
+                    cells.push(index + cols);
+                }
+                if depth + 1 < layers {
+                    cells.push(index + layer);
+                }
+                cells
+            })
+            .collect();
+
+        Cuboid::new(cols, rows, layers, toggles)
+    }
+
+    /// Surface of a cube with `size` x `size` cells on each of its 6 faces, the presses next to an
+    /// edge toggle the cell on the other side of it
+    #[must_use]
+    pub fn new_cube(size: usize) -> Cuboid {
+        let side = size as isize;
+
+        // every cell is a point on the surface, one of its axes sits at -1 or size
+        let mut cells = vec![];
+        for axis in 0..3 {
+            for level in [-1, side] {
+                for row in 0..side {
+                    for col in 0..side {
+                        let mut point = [0; 3];
+                        point[axis] = level;
+                        point[(axis + 1) % 3] = col;
+                        point[(axis + 2) % 3] = row;
+                        cells.push((axis, point));
+                    }
+                }
+            }
+        }
+        let index = cells
+            .iter()
+            .enumerate()
+            .map(|(index, (_, point))| (*point, index))
+            .collect::<HashMap<_, _>>();
+
+        let toggles = cells
+            .iter()
+            .enumerate()
+            .map(|(cell, &(axis, point))| {
+                let mut toggled = vec![cell];
+
+                for direction in [(axis + 1) % 3, (axis + 2) % 3] {
+                    for step in [-1, 1] {
+                        let mut next = point;
+                        next[direction] += step;
+
+                        // crossing an edge moves the point onto the face of `direction`
+                        if !(0..side).contains(&next[direction]) {
+                            next[axis] = next[axis].clamp(0, side - 1);
+                        }
+                        if let Some(&neighbour) = index.get(&next) {
+                            if !toggled.contains(&neighbour) {
+                                toggled.push(neighbour);
+                            }
+                        }
+                    }
+                }
+                toggled
+            })
+            .collect();
+
+        Cuboid::new(size, size, 6, toggles)
+    }
+
+    fn new(cols: usize, rows: usize, layers: usize, toggles: Vec<Vec<usize>>) -> Cuboid {
+        let cells = cols * rows * layers;
+
+        Cuboid {
+            cols,
+            rows,
+            layers,
+            board: vec![0usize; cells],
+            rules: Rules::new(cells),
+            toggles,
+        }
+    }
+
+    /// Turns on the lights at `active`
+    #[must_use]
+    pub fn with_lights(mut self, active: &[usize]) -> Cuboid {
+        active.iter().for_each(|position| self.board[*position] = 1);
+
+        self
+    }
+
+    fn get_index(&self, col: usize, row: usize) -> usize {
+        row * self.cols + col
+    }
+}
+
+impl Board for Cuboid {
+    fn size(&self) -> (usize, usize) {
+        (self.cols, self.rows())
+    }
+
+    fn cols(&self) -> usize {
+        self.cols
+    }
+
+    fn rows(&self) -> usize {
+        self.rows * self.layers
+    }
+
+    fn iter(&self) -> std::slice::Iter<'_, usize> {
+        self.board.iter()
+    }
+
+    fn get(&self, col: usize, row: usize) -> Option<usize> {
+        if col < self.cols && row < self.rows() {
+            Some(self.board[self.get_index(col, row)])
+        } else {
+            None
+        }
+    }
+
+    fn set(&mut self, col: usize, row: usize, value: usize) -> bool {
+        if col < self.cols && row < self.rows() && value <= 1 {
+            let index = self.get_index(col, row);
+            self.board[index] = value;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn rules(&self) -> &Rules {
+        &self.rules
+    }
+
+    fn rules_mut(&mut self) -> &mut Rules {
+        &mut self.rules
+    }
+
+    fn states(&self) -> usize {
+        2
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Board> {
+        Box::new(self.clone())
+    }
+
+    fn trigger_index(&mut self, index: usize) -> &mut dyn Board {
+        if index >= self.board.len() {
+            return self;
+        }
+        for &cell in &self.toggles[index] {
+            self.board[cell] = (self.board[cell] + 1) % 2;
+        }
+        self
+    }
+
+    fn trigger_coord(&mut self, col: usize, row: usize) -> &mut dyn Board {
+        if col >= self.cols || row >= self.rows() {
+            return self;
+        }
+        let index = self.get_index(col, row);
+        self.trigger_index(index)
+    }
+
+    fn toggled_cells(&self, index: usize) -> Vec<usize> {
+        self.toggles[index].clone()
+    }
+
+    /// Every layer on its own separated by an empty line
+    fn draw(&self, cells: &[String]) -> String {
+        cells
+            .chunks(self.cols * self.rows)
+            .map(|layer| grid::draw(Grid::Square, self.cols, layer))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
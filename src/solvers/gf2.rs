@@ -1,91 +1,353 @@
-use super::board::{Binary, Board};
+use log::warn;
 
-pub fn solve(board: &dyn Board) -> Option<Vec<usize>> {
-    let (cols, rows) = board.size();
-
-    let mut matrix = vec![vec![0usize; rows * cols]; rows * cols];
-    let mut blank = Binary::new_blank(cols, rows);
-    let mut expected = vec![0usize; cols * rows];
-
-    for row in 0..rows {
-        for col in 0..cols {
-            let index = row * cols + col;
-            blank.trigger_coord(col, row);
-            for sub_row in 0..rows {
-                for sub_col in 0..cols {
-                    let sub_index = sub_row * cols + sub_col;
-                    matrix[index][sub_index] = blank.get(sub_col, sub_row)?;
-                }
-            }
-            blank.trigger_coord(col, row);
-            expected[index] = (board.get(col, row)? + 1) % 2;
+use super::{
+    bit_matrix::{BitMatrix, BitVector},
+    board::Board,
+    solver::{Capabilities, Solver},
+};
+
+/// Kernels up to this dimension are searched exhaustively, bigger ones fall back to a greedy descent
+const MAX_EXHAUSTIVE_KERNEL: usize = 20;
+
+/// Gaussian elimination over GF(2), it finds the fewest presses or the cheapest ones as long as
+/// the kernel has up to `MAX_EXHAUSTIVE_KERNEL` dimensions
+pub struct Gf2Solver;
+
+impl Solver for Gf2Solver {
+    fn name(&self) -> &'static str {
+        "gf2"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            minimal: true,
+            max_cells: Some(200 * 200),
+            masks: true,
+            multi_state: false,
+            neighbourhoods: true,
+            restricted: true,
+            weighted: true,
         }
     }
 
-    let sol = gauss_jordan_zf2(matrix, expected);
+    fn solve(&self, board: &dyn Board) -> Option<Vec<usize>> {
+        solve(board)
+    }
+}
 
-    sol.map(|solution| {
-        solution
-            .iter()
-            .enumerate()
-            .filter(|(_, val)| **val != 0)
-            .map(|(index, _)| index)
-            .collect::<Vec<usize>>()
-    })
+pub fn solve(board: &dyn Board) -> Option<Vec<usize>> {
+    solution_space(board).minimal(board)
 }
-fn gauss_jordan_zf2(mut mat: Vec<Vec<usize>>, expected: Vec<usize>) -> Option<Vec<usize>> {
-    fn swap(m: &mut [Vec<usize>], sol: &mut [usize], i: usize, j: usize) {
-        if i == j {
-            return;
+
+/// Solves the board keeping track of every possible solution
+pub fn solution_space(board: &dyn Board) -> SolutionSpace {
+    let (matrix, expected) = build_system(board);
+    let mut space = solve_system(matrix, expected);
+
+    // the columns of the presses that aren't allowed are empty so each one adds a vector to
+    // the kernel with only that press, removing it leaves the solutions without them
+    space
+        .kernel
+        .retain(|vector| vector.iter_ones().all(|press| board.is_pressable(press)));
+
+    space
+}
+
+/// Every solution of the system is `particular + any combination of the kernel`
+#[derive(Debug, Clone)]
+pub struct SolutionSpace {
+    /// Rank of the toggle matrix
+    pub rank: usize,
+    /// Solution with every free variable set to 0, `None` if the board is unsolvable
+    pub particular: Option<BitVector>,
+    /// Basis of the null space, the presses that leave the board unchanged
+    pub kernel: Vec<BitVector>,
+}
+
+impl SolutionSpace {
+    #[must_use]
+    pub fn kernel_dimension(&self) -> usize {
+        self.kernel.len()
+    }
+
+    /// Number of solutions `2^k`, `None` when it doesn't fit in 128 bits
+    #[must_use]
+    pub fn solution_count(&self) -> Option<u128> {
+        if self.particular.is_none() {
+            return Some(0);
         }
+        u32::try_from(self.kernel.len())
+            .ok()
+            .and_then(|dimension| 1u128.checked_shl(dimension))
+    }
 
-        m.swap(i, j);
-        sol.swap(i, j);
+    /// Solution with the lowest cost, when every press of the board costs the same it's the one
+    /// with the fewest presses
+    #[must_use]
+    pub fn minimal(&self, board: &dyn Board) -> Option<Vec<usize>> {
+        let particular = self.particular.clone()?;
+        Some(
+            minimise(particular, &self.kernel, board)
+                .iter_ones()
+                .collect(),
+        )
     }
 
-    fn add(m: &mut [Vec<usize>], bs: &mut [usize], i: usize, j: usize) {
-        assert!(i != j, "trying to add row to itself");
+    /// Iterates over every solution, each one as the list of indices to press
+    #[must_use]
+    pub fn solutions(&self) -> Solutions<'_> {
+        Solutions {
+            kernel: &self.kernel,
+            current: self.particular.clone(),
+            step: 0,
+        }
+    }
+}
+
+/// Walks the solutions in gray code order so each step only adds one kernel vector
+pub struct Solutions<'a> {
+    kernel: &'a [BitVector],
+    current: Option<BitVector>,
+    step: u128,
+}
+
+impl Iterator for Solutions<'_> {
+    type Item = Vec<usize>;
 
-        for x in 0..m[i].len() {
-            m[i][x] += m[j][x];
-            m[i][x] %= 2;
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.current.as_mut()?;
+        let solution = current.iter_ones().collect();
+
+        self.step += 1;
+        let bit = self.step.trailing_zeros() as usize;
+        if bit < self.kernel.len() {
+            current.xor(&self.kernel[bit]);
+        } else {
+            self.current = None;
         }
 
-        bs[i] += bs[j];
-        bs[i] %= 2;
+        Some(solution)
     }
+}
+
+/// Builds `A·x = b` where the column `i` of `A` are the cells toggled by pressing `i`
+/// and `b` the cells that have to change to reach the target. Masked cells have no equation
+/// and the columns of the cells that can't be pressed are empty
+fn build_system(board: &dyn Board) -> (BitMatrix, BitVector) {
+    (toggle_matrix(board, false), expected(board))
+}
+
+/// The transposed matrix has the cells toggled by pressing `i` in the row `i`
+fn toggle_matrix(board: &dyn Board, transposed: bool) -> BitMatrix {
+    let cells = board.cols() * board.rows();
+    let constrained = constrained_cells(board);
 
-    let mut solution = expected;
-    let rows = mat.len();
-    if rows == 0 {
-        return None;
+    // position of each cell in the list of equations
+    let mut equation = vec![None; cells];
+    constrained
+        .iter()
+        .enumerate()
+        .for_each(|(position, &cell)| equation[cell] = Some(position));
+
+    let mut matrix = if transposed {
+        BitMatrix::new(cells, constrained.len())
+    } else {
+        BitMatrix::new(constrained.len(), cells)
+    };
+
+    for press in (0..cells).filter(|&press| board.is_pressable(press)) {
+        for row in board
+            .toggled_cells(press)
+            .iter()
+            .filter_map(|&cell| equation[cell])
+        {
+            if transposed {
+                matrix.set(press, row, true);
+            } else {
+                matrix.set(row, press, true);
+            }
+        }
     }
-    let cols = mat[0].len();
-    if cols == 0 {
-        return None;
+
+    matrix
+}
+
+fn expected(board: &dyn Board) -> BitVector {
+    let constrained = constrained_cells(board);
+    let mut expected = BitVector::new(constrained.len());
+
+    for (row, &cell) in constrained.iter().enumerate() {
+        let (col, cell_row) = (cell % board.cols(), cell / board.cols());
+        expected.set(row, board.get(col, cell_row) != Some(board.target(cell)));
     }
 
-    for pivot in 0..rows {
-        // 1. find pivot row
-        for i in pivot..rows {
-            if mat[i][pivot] != 0 {
-                swap(&mut mat, &mut solution, i, pivot);
-                break;
+    expected
+}
+
+/// Cells with an equation, the ones that are not masked
+fn constrained_cells(board: &dyn Board) -> Vec<usize> {
+    (0..board.cols() * board.rows())
+        .filter(|&cell| !board.is_masked(cell))
+        .collect()
+}
+
+/// Group of cells where every press toggles an even number of them
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuietPattern {
+    pub cells: Vec<usize>,
+    /// Cells of the pattern that have to change, when there is an odd number of them
+    /// no combination of presses can solve the board
+    pub violations: Vec<usize>,
+}
+
+/// Quiet patterns that prove the board can't be solved, empty when it has a solution
+pub fn explain_unsolvable(board: &dyn Board) -> Vec<QuietPattern> {
+    let constrained = constrained_cells(board);
+    let expected = expected(board);
+
+    // the quiet patterns are the null space of the transposed matrix
+    let space = solve_system(
+        toggle_matrix(board, true),
+        BitVector::new(board.cols() * board.rows()),
+    );
+
+    space
+        .kernel
+        .iter()
+        .filter(|pattern| pattern.dot(&expected))
+        .map(|pattern| QuietPattern {
+            cells: pattern.iter_ones().map(|row| constrained[row]).collect(),
+            violations: pattern
+                .iter_ones()
+                .filter(|&row| expected.get(row))
+                .map(|row| constrained[row])
+                .collect(),
+        })
+        .collect()
+}
+
+/// Reduces the system to its row echelon form and returns one particular solution
+/// (every free variable set to 0) together with a basis of the null space
+pub(crate) fn solve_system(mut matrix: BitMatrix, mut expected: BitVector) -> SolutionSpace {
+    let rows = matrix.rows();
+    let cols = matrix.cols();
+    let mut pivots: Vec<usize> = vec![];
+
+    for col in 0..cols {
+        let rank = pivots.len();
+        if rank == rows {
+            break;
+        }
+
+        // 1. find pivot row, if there is none the variable is free
+        let Some(pivot) = (rank..rows).find(|&row| matrix.get(row, col)) else {
+            continue;
+        };
+        matrix.swap_rows(pivot, rank);
+        expected.swap(pivot, rank);
+
+        // 2. add pivot to all the rows below that have 1 in this column
+        for row in rank + 1..rows {
+            if matrix.get(row, col) {
+                matrix.xor_rows(row, rank, col);
+                if expected.get(rank) {
+                    expected.toggle(row);
+                }
             }
         }
-        if mat[pivot][pivot] == 0 && solution[pivot] != 0 {
-            return None;
+
+        pivots.push(col);
+    }
+
+    // rows without pivot read 0 = 1 when the system is inconsistent
+    let solvable = (pivots.len()..rows).all(|row| !expected.get(row));
+
+    // the bits before each pivot are 0 so going upwards every other variable in the row is already known
+    let back_substitute = |mut vector: BitVector, rhs: &dyn Fn(usize) -> bool| {
+        for (row, &col) in pivots.iter().enumerate().rev() {
+            let value = rhs(row) ^ matrix.row(row).dot(&vector);
+            vector.set(col, value);
+        }
+        vector
+    };
+
+    let particular =
+        solvable.then(|| back_substitute(BitVector::new(cols), &|row| expected.get(row)));
+
+    let mut is_pivot = vec![false; cols];
+    pivots.iter().for_each(|&col| is_pivot[col] = true);
+
+    let kernel = (0..cols)
+        .filter(|&col| !is_pivot[col])
+        .map(|free| {
+            let mut vector = BitVector::new(cols);
+            vector.set(free, true);
+            back_substitute(vector, &|_| false)
+        })
+        .collect::<Vec<_>>();
+
+    SolutionSpace {
+        rank: pivots.len(),
+        particular,
+        kernel,
+    }
+}
+
+/// Every solution is `particular + combination of the kernel`, this picks the cheapest one
+/// which is the one with less presses unless the costs of the board are different. Above
+/// `MAX_EXHAUSTIVE_KERNEL` dimensions the greedy descent may stop at a more expensive one
+pub(crate) fn minimise(
+    particular: BitVector,
+    kernel: &[BitVector],
+    board: &dyn Board,
+) -> BitVector {
+    let costs = (0..particular.len())
+        .map(|press| board.cost(press))
+        .collect::<Vec<_>>();
+    let uniform = costs.windows(2).all(|pair| pair[0] == pair[1]);
+    let weight = |presses: &BitVector| -> usize {
+        if uniform {
+            presses.count_ones()
+        } else {
+            presses.iter_ones().map(|press| costs[press]).sum()
         }
+    };
+    let mut best_weight = weight(&particular);
+    let mut best = particular;
+
+    if kernel.len() <= MAX_EXHAUSTIVE_KERNEL {
+        // walk the 2^k combinations in gray code order so each step only adds one vector
+        let mut current = best.clone();
+        for step in 1usize..(1 << kernel.len()) {
+            current.xor(&kernel[step.trailing_zeros() as usize]);
 
-        // 2. add pivot to all rows that have 1 in this column
-        for i in 0..rows {
-            if i == pivot {
-                continue;
+            let current_weight = weight(&current);
+            if current_weight < best_weight {
+                best_weight = current_weight;
+                best.clone_from(&current);
             }
-            if mat[i][pivot] != 0 {
-                add(&mut mat, &mut solution, i, pivot);
+        }
+    } else {
+        warn!(
+            "The kernel has {} dimensions, the solution may not be the minimal one",
+            kernel.len()
+        );
+        let mut improved = true;
+        while improved {
+            improved = false;
+            for vector in kernel {
+                let mut candidate = best.clone();
+                candidate.xor(vector);
+
+                let candidate_weight = weight(&candidate);
+                if candidate_weight < best_weight {
+                    best_weight = candidate_weight;
+                    best = candidate;
+                    improved = true;
+                }
             }
         }
     }
-    Some(solution)
+
+    best
 }
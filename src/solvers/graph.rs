@@ -0,0 +1,289 @@
+use super::board::{Board, Rules};
+
+/// Board made of nodes where pressing one toggles it and its neighbours, the σ+ game.
+/// The nodes are laid out in a single row so `col` is the index of the node
+#[derive(Debug, Clone)]
+pub struct Graph {
+    names: Vec<String>,
+    adjacency: Vec<Vec<usize>>,
+    board: Vec<usize>,
+    rules: Rules,
+}
+
+impl Graph {
+    /// Blank graph with an edge between every pair of `edges`, self loops are ignored
+    #[must_use]
+    pub fn new_blank(names: Vec<String>, edges: &[(usize, usize)]) -> Graph {
+        let nodes = names.len();
+        let mut adjacency = vec![vec![]; nodes];
+
+        for &(a, b) in edges {
+            if a != b && !adjacency[a].contains(&b) {
+                adjacency[a].push(b);
+                adjacency[b].push(a);
+            }
+        }
+
+        Graph {
+            names,
+            adjacency,
+            board: vec![0usize; nodes],
+            rules: Rules::new(nodes),
+        }
+    }
+
+    /// Reads a graph in DOT format when the text has a `graph {` block, otherwise as an edge list
+    pub fn parse(text: &str) -> Result<Graph, String> {
+        let is_dot = text
+            .split_once('{')
+            .is_some_and(|(header, _)| header.contains("graph"));
+
+        if is_dot {
+            Self::from_dot(text)
+        } else {
+            Self::from_edge_list(text)
+        }
+    }
+
+    /// Every line is an edge between 2 nodes or a single node without edges, like `a b`.
+    /// Empty lines and the ones starting with `#` are ignored
+    pub fn from_edge_list(text: &str) -> Result<Graph, String> {
+        let mut nodes = Nodes::default();
+        let mut edges = vec![];
+
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let names = line
+                .split(|c: char| c.is_whitespace() || c == ',')
+                .filter(|name| !name.is_empty())
+                .collect::<Vec<_>>();
+
+            match names[..] {
+                [name] => {
+                    nodes.index(name);
+                }
+                [a, b] => edges.push((nodes.index(a), nodes.index(b))),
+                _ => {
+                    return Err(format!(
+                        "Line {}: expected 1 or 2 nodes but found {}",
+                        number + 1,
+                        names.len()
+                    ))
+                }
+            }
+        }
+
+        Ok(nodes.into_graph(&edges))
+    }
+
+    /// Reads the node and edge statements of a DOT graph, attributes and subgraphs are ignored
+    pub fn from_dot(text: &str) -> Result<Graph, String> {
+        let text = text
+            .lines()
+            .map(|line| line.split("//").next().unwrap_or_default())
+            .filter(|line| !line.trim_start().starts_with('#'))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let (Some(start), Some(end)) = (text.find('{'), text.rfind('}')) else {
+            return Err("The DOT graph needs a body between '{' and '}'".to_string());
+        };
+        if end < start {
+            return Err("The DOT graph needs a body between '{' and '}'".to_string());
+        }
+
+        let mut nodes = Nodes::default();
+        let mut edges = vec![];
+
+        for statement in text[start + 1..end].split([';', '\n', '{', '}']) {
+            let statement = statement
+                .split('[')
+                .next()
+                .unwrap_or_default()
+                .replace("->", "--");
+            let statement = statement.trim();
+
+            if statement.is_empty()
+                || statement.contains('=')
+                || ["graph", "node", "edge", "subgraph"]
+                    .iter()
+                    .any(|keyword| statement.split_whitespace().next() == Some(keyword))
+            {
+                continue;
+            }
+
+            let names = statement
+                .split("--")
+                .map(|name| name.trim().trim_matches('"'))
+                .collect::<Vec<_>>();
+            if names.iter().any(|name| name.is_empty()) {
+                return Err(format!("Invalid statement '{statement}'"));
+            }
+
+            let indices = names
+                .iter()
+                .map(|name| nodes.index(name))
+                .collect::<Vec<_>>();
+            edges.extend(indices.windows(2).map(|pair| (pair[0], pair[1])));
+        }
+
+        Ok(nodes.into_graph(&edges))
+    }
+
+    /// Turns on the lights at `active`
+    #[must_use]
+    pub fn with_lights(mut self, active: &[usize]) -> Graph {
+        active.iter().for_each(|position| self.board[*position] = 1);
+
+        self
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
+
+/// Names of the nodes in the order they show up
+#[derive(Default)]
+struct Nodes {
+    names: Vec<String>,
+}
+
+impl Nodes {
+    fn index(&mut self, name: &str) -> usize {
+        self.names
+            .iter()
+            .position(|known| known == name)
+            .unwrap_or_else(|| {
+                self.names.push(name.to_string());
+                self.names.len() - 1
+            })
+    }
+
+    /// When every name is a number the nodes are sorted by it, otherwise they keep their order
+    fn into_graph(self, edges: &[(usize, usize)]) -> Graph {
+        let numbers = self
+            .names
+            .iter()
+            .map(|name| name.parse::<usize>().ok())
+            .collect::<Option<Vec<_>>>();
+
+        let Some(numbers) = numbers else {
+            return Graph::new_blank(self.names, edges);
+        };
+
+        let mut order = (0..self.names.len()).collect::<Vec<_>>();
+        order.sort_by_key(|&index| numbers[index]);
+        let mut position = vec![0; order.len()];
+        order
+            .iter()
+            .enumerate()
+            .for_each(|(sorted, &index)| position[index] = sorted);
+
+        let names = order
+            .iter()
+            .map(|&index| self.names[index].clone())
+            .collect();
+        let edges = edges
+            .iter()
+            .map(|&(a, b)| (position[a], position[b]))
+            .collect::<Vec<_>>();
+
+        Graph::new_blank(names, &edges)
+    }
+}
+
+impl Board for Graph {
+    fn size(&self) -> (usize, usize) {
+        (self.len(), 1)
+    }
+
+    fn cols(&self) -> usize {
+        self.len()
+    }
+
+    fn rows(&self) -> usize {
+        1
+    }
+
+    fn iter(&self) -> std::slice::Iter<'_, usize> {
+        self.board.iter()
+    }
+
+    fn get(&self, col: usize, row: usize) -> Option<usize> {
+        if row == 0 {
+            self.board.get(col).copied()
+        } else {
+            None
+        }
+    }
+
+    fn set(&mut self, col: usize, row: usize, value: usize) -> bool {
+        if row == 0 && col < self.len() && value <= 1 {
+            self.board[col] = value;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn rules(&self) -> &Rules {
+        &self.rules
+    }
+
+    fn rules_mut(&mut self) -> &mut Rules {
+        &mut self.rules
+    }
+
+    fn states(&self) -> usize {
+        2
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Board> {
+        Box::new(self.clone())
+    }
+
+    fn trigger_index(&mut self, index: usize) -> &mut dyn Board {
+        if index >= self.board.len() {
+            return self;
+        }
+        for cell in self.toggled_cells(index) {
+            self.board[cell] = (self.board[cell] + 1) % 2;
+        }
+        self
+    }
+
+    fn trigger_coord(&mut self, col: usize, row: usize) -> &mut dyn Board {
+        if row != 0 {
+            return self;
+        }
+        self.trigger_index(col)
+    }
+
+    fn toggled_cells(&self, index: usize) -> Vec<usize> {
+        std::iter::once(index)
+            .chain(self.adjacency[index].iter().copied())
+            .collect()
+    }
+
+    /// One line per node with its name
+    fn draw(&self, cells: &[String]) -> String {
+        let width = self.names.iter().map(String::len).max().unwrap_or_default();
+
+        self.names
+            .iter()
+            .zip(cells)
+            .map(|(name, cell)| format!("\n{name:>width$} {cell}"))
+            .collect()
+    }
+}
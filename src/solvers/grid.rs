@@ -0,0 +1,89 @@
+use crate::args::Grid;
+
+/// The cell at `index` and its 6 neighbours on a hex grid where the odd rows are shifted half a cell to the right
+#[must_use]
+pub fn hex_neighbours(cols: usize, rows: usize, index: usize) -> Vec<usize> {
+    let col = (index % cols) as isize;
+    let row = (index / cols) as isize;
+    let shift = row % 2;
+
+    let offsets = [
+        (0, 0),
+        (shift - 1, -1),
+        (shift, -1),
+        (-1, 0),
+        (1, 0),
+        (shift - 1, 1),
+        (shift, 1),
+    ];
+
+    offsets
+        .iter()
+        .map(|&(col_offset, row_offset)| (col + col_offset, row + row_offset))
+        .filter(|&(col, row)| {
+            (0..cols as isize).contains(&col) && (0..rows as isize).contains(&row)
+        })
+        .map(|(col, row)| row as usize * cols + col as usize)
+        .collect()
+}
+
+/// The cell at `index` and the 3 triangles sharing an edge with it, the cells pointing up
+/// are the ones where `col + row` is even and their base touches the row below
+#[must_use]
+pub fn triangle_neighbours(cols: usize, rows: usize, index: usize) -> Vec<usize> {
+    let col = index % cols;
+    let row = index / cols;
+    let mut cells = vec![index];
+
+    if col > 0 {
+        cells.push(index - 1);
+    }
+    if col + 1 < cols {
+        cells.push(index + 1);
+    }
+    if (col + row).is_multiple_of(2) {
+        if row + 1 < rows {
+            cells.push(index + cols);
+        }
+    } else if row > 0 {
+        cells.push(index - cols);
+    }
+
+    cells
+}
+
+/// Lays out one string per cell following the shape of the grid
+#[must_use]
+pub fn draw(grid: Grid, cols: usize, cells: &[String]) -> String {
+    let mut drawing = String::new();
+
+    for (row, line) in cells.chunks(cols).enumerate() {
+        drawing.push('\n');
+
+        match grid {
+            Grid::Square => line.iter().for_each(|cell| drawing.push_str(cell)),
+            Grid::Hex => {
+                if row % 2 == 1 {
+                    drawing.push(' ');
+                }
+                drawing.push_str(&line.join(" "));
+            }
+            Grid::Triangle => {
+                // lights keep the direction of the triangle, the rest of values are drawn as they are
+                for (col, cell) in line.iter().enumerate() {
+                    let up = (col + row).is_multiple_of(2);
+                    let cell = match (cell.as_str(), up) {
+                        ("·", true) => "△",
+                        ("·", false) => "▽",
+                        ("#", true) => "▲",
+                        ("#", false) => "▼",
+                        (cell, _) => cell,
+                    };
+                    drawing.push_str(cell);
+                }
+            }
+        }
+    }
+
+    drawing
+}
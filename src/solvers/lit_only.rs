@@ -0,0 +1,103 @@
+use std::collections::VecDeque;
+
+use super::{
+    board::Board,
+    solver::{Capabilities, Solver},
+};
+
+/// Breadth first search over the states of the board where only the lit cells can be pressed,
+/// the lit-only σ game. The order of the presses matters and a cell can be pressed more than once
+pub struct LitOnlySolver;
+
+impl Solver for LitOnlySolver {
+    fn name(&self) -> &'static str {
+        "lit-only"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            minimal: true,
+            max_cells: Some(25),
+            masks: true,
+            multi_state: false,
+            neighbourhoods: true,
+            restricted: true,
+            weighted: false,
+        }
+    }
+
+    fn solve(&self, board: &dyn Board) -> Option<Vec<usize>> {
+        solve(board)
+    }
+}
+
+/// Presses in the order they have to be made, every state of the board is a bit set so
+/// the search keeps the last press that reached each of them
+pub fn solve(board: &dyn Board) -> Option<Vec<usize>> {
+    let cells = board.cols() * board.rows();
+    let bit = |index: usize| 1u32 << index;
+
+    let start = board
+        .iter()
+        .enumerate()
+        .filter(|(_, &value)| value != 0)
+        .fold(0, |state, (index, _)| state | bit(index));
+    let (target, care) = (0..cells).filter(|&index| !board.is_masked(index)).fold(
+        (0, 0),
+        |(target, care), index| {
+            let target = if board.target(index) != 0 {
+                target | bit(index)
+            } else {
+                target
+            };
+            (target, care | bit(index))
+        },
+    );
+    let toggles = (0..cells)
+        .map(|index| {
+            board
+                .toggled_cells(index)
+                .into_iter()
+                .fold(0, |toggle, cell| toggle | bit(cell))
+        })
+        .collect::<Vec<u32>>();
+    let presses = (0..cells)
+        .filter(|&index| board.is_pressable(index))
+        .collect::<Vec<_>>();
+
+    // 0 means not visited yet, the start is marked with u8::MAX and the rest of states keep
+    // the press that reached them plus 1
+    let mut previous = vec![0u8; 1 << cells];
+    previous[start as usize] = u8::MAX;
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(state) = queue.pop_front() {
+        if (state ^ target) & care == 0 {
+            return Some(path(state, &previous, &toggles));
+        }
+
+        for &press in presses.iter().filter(|&&press| state & bit(press) != 0) {
+            let next = state ^ toggles[press];
+            if previous[next as usize] == 0 {
+                previous[next as usize] = press as u8 + 1;
+                queue.push_back(next);
+            }
+        }
+    }
+
+    None
+}
+
+/// Walks back from `state` to the start undoing the presses
+fn path(mut state: u32, previous: &[u8], toggles: &[u32]) -> Vec<usize> {
+    let mut presses = vec![];
+
+    while previous[state as usize] != u8::MAX {
+        let press = usize::from(previous[state as usize] - 1);
+        presses.push(press);
+        state ^= toggles[press];
+    }
+    presses.reverse();
+
+    presses
+}
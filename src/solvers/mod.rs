@@ -1,18 +1,73 @@
+pub mod bit_matrix;
 pub mod board;
+pub mod chase;
+pub mod cuboid;
 pub mod gf2;
+pub mod graph;
+pub mod grid;
+pub mod lit_only;
+pub mod neighbourhood;
+pub mod per_cell;
 pub mod recursive;
+pub mod solver;
+pub mod zk;
 
 #[cfg(test)]
 mod solver_tests {
+    use crate::args::{Grid, Wrap};
     use crate::solvers::{
+        bit_matrix::BitVector,
         board::{Binary, Board},
-        gf2, recursive,
+        chase,
+        cuboid::Cuboid,
+        gf2,
+        graph::Graph,
+        lit_only,
+        neighbourhood::Neighbourhood,
+        per_cell::PerCell,
+        recursive,
+        solver::{self, Solver},
+        zk,
     };
 
     fn assert_board_eq(board: &dyn Board, expected: &[usize]) {
         assert!(board.iter().zip(expected.iter()).all(|(&a, &b)| a == b));
     }
 
+    /// Solves the board with every solver of the registry and checks their solutions, only the
+    /// solvers in `expected_skips` may be unable to handle it. The boards made by pressing some
+    /// buttons on a solved board can always be solved
+    fn assert_registry_solves(
+        board: &dyn Board,
+        expected_skips: &[&str],
+    ) -> Vec<(Box<dyn Solver>, Vec<usize>)> {
+        solver::registry()
+            .into_iter()
+            .filter(|solver| {
+                let can_solve = solver.can_solve(board);
+                assert!(
+                    can_solve || expected_skips.contains(&solver.name()),
+                    "{} can't handle the board",
+                    solver.name()
+                );
+                can_solve
+            })
+            .map(|solver| {
+                let solution = solver
+                    .solve(board)
+                    .unwrap_or_else(|| panic!("{} found no solution", solver.name()));
+
+                let mut solved = board.boxed_clone();
+                for &step in &solution {
+                    solved.trigger_index(step);
+                }
+                assert!(solved.is_solved(), "{} failed", solver.name());
+
+                (solver, solution)
+            })
+            .collect()
+    }
+
     #[test]
     fn board_simulate_tl() {
         let mut board = Binary::new_blank(3, 3);
@@ -138,4 +193,642 @@ mod solver_tests {
 
         assert_eq!(solution, [4]);
     }
+
+    fn brute_force_minimum(board: &Binary) -> usize {
+        let cells = board.cols() * board.rows();
+        (0usize..1 << cells)
+            .filter(|presses| {
+                let mut copy = Binary::new_from_values(
+                    &board.iter().map(|&b| b != 0).collect::<Vec<_>>(),
+                    board.cols(),
+                    board.rows(),
+                );
+                (0..cells)
+                    .filter(|i| presses & (1 << i) != 0)
+                    .for_each(|i| {
+                        copy.trigger_index(i);
+                    });
+                copy.is_solved()
+            })
+            .map(|presses| presses.count_ones() as usize)
+            .min()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_gf2_minimun_solution_5x5() {
+        let mut board = Binary::new_blank(5, 5);
+
+        let solution = gf2::solve(&board).unwrap();
+
+        for &step in &solution {
+            board.trigger_index(step);
+        }
+
+        assert!(board.is_solved());
+        assert_eq!(solution.len(), 15);
+    }
+
+    #[test]
+    fn test_gf2_minimun_solution_4x4() {
+        let presses: [&[usize]; 3] = [&[], &[0, 5, 10, 15], &[1, 2, 7, 8, 12]];
+
+        for steps in presses {
+            // pressing buttons on a blank board always leads to a solvable board
+            let mut board = Binary::new_blank(4, 4);
+            steps.iter().for_each(|&step| {
+                board.trigger_index(step);
+            });
+
+            let solution = gf2::solve(&board).unwrap();
+
+            assert_eq!(solution.len(), brute_force_minimum(&board));
+        }
+    }
+
+    #[test]
+    fn test_bit_vector() {
+        let mut a = BitVector::new(130);
+        let mut b = BitVector::new(130);
+        [0, 64, 129].iter().for_each(|&i| a.set(i, true));
+        [64, 65].iter().for_each(|&i| b.set(i, true));
+
+        assert!(a.dot(&b));
+        a.xor(&b);
+        assert_eq!(a.iter_ones().collect::<Vec<_>>(), [0, 65, 129]);
+        assert_eq!(a.count_ones(), 3);
+    }
+
+    #[test]
+    fn test_gf2_solves_large() {
+        let mut board = Binary::new_from_positions(&[0, 17, 450, 1599], 40, 40);
+
+        let solution = gf2::solve(&board).unwrap();
+
+        for &step in &solution {
+            board.trigger_index(step);
+        }
+
+        assert!(board.is_solved());
+    }
+
+    #[test]
+    fn test_chase_solves() {
+        let mut board = Binary::new_from_positions(&[0, 17, 450, 1599], 40, 50);
+
+        let solution = chase::solve(&board).unwrap();
+
+        for &step in &solution {
+            board.trigger_index(step);
+        }
+
+        assert!(board.is_solved());
+    }
+
+    #[test]
+    fn test_chase_matches_gf2() {
+        for (cols, rows) in [(3, 3), (4, 4), (5, 5), (7, 2), (1, 6)] {
+            let board = Binary::new_blank(cols, rows);
+
+            let chased = chase::solve(&board).unwrap();
+            let solution = gf2::solve(&board).unwrap();
+
+            assert_eq!(chased.len(), solution.len(), "{cols}x{rows} board");
+        }
+    }
+
+    #[test]
+    fn test_registry_solves() {
+        let board = Binary::new_from_positions(&[0, 2, 6, 8], 3, 3);
+
+        for (solver, solution) in assert_registry_solves(&board, &[]) {
+            assert_eq!(solution, [4], "{} failed", solver.name());
+        }
+    }
+
+    #[test]
+    fn test_select_solver() {
+        assert_eq!(
+            solver::select(&Binary::new_blank(3, 3)).unwrap().name(),
+            "chase"
+        );
+        assert_eq!(
+            solver::select(&Binary::new_blank(3, 3).with_mask(&[4]))
+                .unwrap()
+                .name(),
+            "gf2"
+        );
+        assert_eq!(
+            solver::select(&Binary::new_blank(300, 300)).unwrap().name(),
+            "chase"
+        );
+    }
+
+    #[test]
+    fn test_gf2_solution_space() {
+        let board = Binary::new_blank(4, 4);
+
+        let space = gf2::solution_space(&board);
+
+        assert_eq!(space.rank, 12);
+        assert_eq!(space.kernel_dimension(), 4);
+        assert_eq!(space.solution_count(), Some(16));
+
+        let mut solutions = space.solutions().collect::<Vec<_>>();
+        assert_eq!(solutions.len(), 16);
+
+        for solution in &solutions {
+            let mut board = Binary::new_blank(4, 4);
+            for &step in solution {
+                board.trigger_index(step);
+            }
+            assert!(board.is_solved());
+        }
+
+        solutions.sort();
+        solutions.dedup();
+        assert_eq!(solutions.len(), 16);
+    }
+
+    #[test]
+    fn test_gf2_solution_space_unsolvable() {
+        let board = Binary::new_from_positions(&[0], 4, 4);
+
+        let space = gf2::solution_space(&board);
+
+        assert_eq!(space.solution_count(), Some(0));
+        assert_eq!(space.solutions().count(), 0);
+        assert!(space.minimal(&board).is_none());
+    }
+
+    #[test]
+    fn test_gf2_explain_unsolvable() {
+        let unsolvable = Binary::new_from_positions(&[0], 4, 4);
+        let solvable = Binary::new_blank(4, 4);
+
+        let diagnosis = gf2::explain_unsolvable(&unsolvable);
+
+        assert!(!diagnosis.is_empty());
+        for pattern in diagnosis {
+            assert_eq!(pattern.violations.len() % 2, 1);
+            for press in 0..16 {
+                let toggled = unsolvable
+                    .toggled_cells(press)
+                    .iter()
+                    .filter(|cell| pattern.cells.contains(cell))
+                    .count();
+                assert_eq!(toggled % 2, 0);
+            }
+        }
+
+        assert!(gf2::explain_unsolvable(&solvable).is_empty());
+    }
+
+    #[test]
+    fn test_solvers_already_solved() {
+        let board = Binary::new_blank(3, 3).with_target(&[]);
+
+        for (solver, solution) in assert_registry_solves(&board, &[]) {
+            assert!(
+                solution.is_empty(),
+                "{} pressed {solution:?}",
+                solver.name()
+            );
+        }
+    }
+
+    #[test]
+    fn test_solvers_reach_target() {
+        let targets: [&[usize]; 3] = [&[], &[0, 2, 4, 6, 8], &[1, 3, 5, 7]];
+
+        for target in targets {
+            let board = Binary::new_from_positions(&[0, 4], 3, 3).with_target(target);
+
+            assert_registry_solves(&board, &[]);
+        }
+    }
+
+    #[test]
+    fn test_gf2_dont_care() {
+        let mut board = Binary::new_from_positions(&[0], 4, 4);
+        assert!(gf2::solve(&board).is_none());
+
+        board = board.with_mask(&[0, 1]);
+        let solution = gf2::solve(&board).unwrap();
+
+        for &step in &solution {
+            board.trigger_index(step);
+        }
+
+        assert!(board.is_solved());
+        assert!(!chase::ChaseSolver.can_solve(&board));
+        assert_eq!(solver::select(&board).unwrap().name(), "gf2");
+    }
+
+    #[test]
+    fn test_zk_solves() {
+        for (states, cols, rows) in [(2, 4, 4), (3, 3, 3), (4, 4, 4), (6, 5, 5), (12, 3, 4)] {
+            let mut board = Binary::new_blank(cols, rows)
+                .with_states(states)
+                .with_target(&[]);
+            for step in [0, 0, 3, 5, 7, 7, 7, 8] {
+                board.trigger_index(step);
+            }
+
+            let solution = zk::solve(&board).unwrap();
+
+            for &step in &solution {
+                board.trigger_index(step);
+            }
+
+            assert!(board.is_solved(), "{states} states {cols}x{rows} board");
+        }
+    }
+
+    #[test]
+    fn test_zk_reach_target() {
+        let mut board = Binary::new_blank(3, 3)
+            .with_states(3)
+            .with_values(&[0, 1, 2, 0, 1, 2, 0, 1, 2])
+            .with_target(&[0, 0, 1, 1, 2, 2, 3, 4, 5]);
+
+        let solution = zk::solve(&board).unwrap();
+
+        for &step in &solution {
+            board.trigger_index(step);
+        }
+
+        assert!(board.is_solved());
+        assert_eq!(solver::select(&board).unwrap().name(), "zk");
+    }
+
+    #[test]
+    fn board_states() {
+        // every number of states has the same default goal, all the lights at 1
+        for states in [2, 3, 5] {
+            let mut board = Binary::new_blank(2, 1).with_states(states);
+            assert_eq!(board.target(0), 1);
+            assert!(board.set(0, 0, states - 1));
+            assert!(!board.set(0, 0, states));
+
+            board.trigger_index(0);
+            assert_board_eq(&board, &[0, 1]);
+        }
+    }
+
+    #[test]
+    fn board_simulate_wrap() {
+        let mut board = Binary::new_blank(3, 3).with_wrap(Wrap::Both);
+        board.trigger_index(0);
+
+        let expected = [
+            1, 1, 1, //
+            1, 0, 0, //
+            1, 0, 0, //
+        ];
+        assert_board_eq(&board, &expected);
+    }
+
+    #[test]
+    fn test_solvers_wrap() {
+        for wrap in [Wrap::Horizontal, Wrap::Vertical, Wrap::Both] {
+            let mut board = Binary::new_blank(3, 4).with_target(&[]).with_wrap(wrap);
+            board.trigger_index(0).trigger_index(5);
+
+            assert_registry_solves(&board, &[]);
+        }
+    }
+
+    #[test]
+    fn test_chase_matches_gf2_wrap() {
+        for wrap in [Wrap::Horizontal, Wrap::Vertical, Wrap::Both] {
+            for (cols, rows) in [(3, 3), (5, 5), (4, 6), (7, 2), (2, 7), (1, 6)] {
+                let board = Binary::new_from_positions(&[0, 3], cols, rows).with_wrap(wrap);
+
+                let chased = chase::solve(&board);
+                let solution = gf2::solve(&board);
+
+                assert_eq!(
+                    chased.map(|s| s.len()),
+                    solution.map(|s| s.len()),
+                    "{cols}x{rows} board with {wrap:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_neighbourhood_from_str() {
+        assert_eq!("moore".parse(), Ok(Neighbourhood::Moore));
+        assert_eq!("x".parse(), Ok(Neighbourhood::Cross));
+        assert_eq!(
+            "0,0;1,0;-1,0;0,2".parse(),
+            Ok(Neighbourhood::Stencil(vec![
+                (0, 0),
+                (1, 0),
+                (-1, 0),
+                (0, 2)
+            ]))
+        );
+        assert!("0,0;1".parse::<Neighbourhood>().is_err());
+        assert!("0,a".parse::<Neighbourhood>().is_err());
+    }
+
+    #[test]
+    fn board_simulate_neighbourhood() {
+        let mut board = Binary::new_blank(3, 3).with_neighbourhood(Neighbourhood::Cross);
+        board.trigger_index(4);
+        assert_board_eq(&board, &[1, 0, 1, 0, 1, 0, 1, 0, 1]);
+
+        let mut board = Binary::new_blank(3, 3).with_neighbourhood(Neighbourhood::Knight);
+        board.trigger_index(0);
+        assert_board_eq(&board, &[1, 0, 0, 0, 0, 1, 0, 1, 0]);
+
+        let stencil = "0,0;0,2".parse().unwrap();
+        let mut board = Binary::new_blank(3, 3)
+            .with_neighbourhood(stencil)
+            .with_wrap(Wrap::Vertical);
+        board.trigger_index(7);
+        assert_board_eq(&board, &[0, 0, 0, 0, 1, 0, 0, 1, 0]);
+    }
+
+    #[test]
+    fn test_solvers_neighbourhood() {
+        let neighbourhoods = [
+            Neighbourhood::Cross,
+            Neighbourhood::Moore,
+            Neighbourhood::Knight,
+            "0,0;1,0;-1,0;0,2".parse().unwrap(),
+        ];
+
+        for neighbourhood in neighbourhoods {
+            let mut board = Binary::new_blank(4, 4)
+                .with_target(&[])
+                .with_neighbourhood(neighbourhood);
+            board.trigger_index(1).trigger_index(10);
+
+            assert_registry_solves(&board, &["chase"]);
+        }
+    }
+
+    #[test]
+    fn test_chase_plus_shaped() {
+        let plus =
+            Binary::new_blank(5, 5).with_neighbourhood("0,0;0,1;0,-1;1,0;-1,0".parse().unwrap());
+        assert!(chase::ChaseSolver.can_solve(&plus));
+
+        let moore = Binary::new_blank(5, 5).with_neighbourhood(Neighbourhood::Moore);
+        assert!(!chase::ChaseSolver.can_solve(&moore));
+        assert_eq!(solver::select(&moore).unwrap().name(), "gf2");
+    }
+
+    #[test]
+    fn test_solvers_per_cell() {
+        // Merlin's Magic Square with the indices starting at the top left
+        let toggles = vec![
+            vec![0, 1, 3, 4],
+            vec![0, 1, 2],
+            vec![1, 2, 4, 5],
+            vec![0, 3, 6],
+            vec![1, 3, 4, 5, 7],
+            vec![2, 5, 8],
+            vec![3, 4, 6, 7],
+            vec![6, 7, 8],
+            vec![4, 5, 7, 8],
+        ];
+
+        let board = PerCell::new_from_positions(&[0, 1, 2, 3, 5, 6, 7, 8], toggles, 3, 3);
+
+        for (solver, solution) in assert_registry_solves(&board, &["chase"]) {
+            if solver.capabilities().minimal {
+                assert_eq!(solution.len(), 5, "{} isn't minimal", solver.name());
+            }
+        }
+    }
+
+    #[test]
+    fn test_graph_parse() {
+        let graph = Graph::from_edge_list("# path\n3 2\n2 1\n4\n").unwrap();
+        assert_eq!(graph.len(), 4);
+        assert_eq!(graph.toggled_cells(0), vec![0, 1]);
+        assert_eq!(graph.toggled_cells(1), vec![1, 2, 0]);
+        assert_eq!(graph.toggled_cells(3), vec![3]);
+
+        let dot = "graph g {\n  a -- b -- c [color=red];\n  d; // alone\n  node [shape=box];\n}";
+        let graph = Graph::parse(dot).unwrap();
+        assert_eq!(graph.len(), 4);
+        assert_eq!(graph.toggled_cells(1), vec![1, 0, 2]);
+        assert_eq!(graph.toggled_cells(3), vec![3]);
+
+        assert!(Graph::from_edge_list("a b c").is_err());
+        assert!(Graph::from_dot("graph g { a -- ; }").is_err());
+    }
+
+    #[test]
+    fn test_solvers_graph() {
+        let petersen = "graph petersen {
+            0 -- 1 -- 2 -- 3 -- 4 -- 0;
+            0 -- 5; 1 -- 6; 2 -- 7; 3 -- 8; 4 -- 9;
+            5 -- 7 -- 9 -- 6 -- 8 -- 5;
+        }";
+
+        let board = Graph::parse(petersen).unwrap();
+
+        assert_registry_solves(&board, &["chase"]);
+    }
+
+    #[test]
+    fn board_simulate_hex() {
+        // odd rows are shifted to the right
+        let mut board = Binary::new_blank(3, 3).with_grid(Grid::Hex);
+        board.trigger_index(4);
+        assert_board_eq(&board, &[0, 1, 1, 1, 1, 1, 0, 1, 1]);
+
+        let mut board = Binary::new_blank(3, 3).with_grid(Grid::Hex);
+        board.trigger_index(3);
+        assert_board_eq(&board, &[1, 1, 0, 1, 1, 0, 1, 1, 0]);
+    }
+
+    #[test]
+    fn test_solvers_hex() {
+        let mut board = Binary::new_blank(4, 4)
+            .with_target(&[])
+            .with_grid(Grid::Hex);
+        board.trigger_index(2).trigger_index(9);
+
+        assert_registry_solves(&board, &["chase"]);
+    }
+
+    #[test]
+    fn board_simulate_triangle() {
+        // the top left triangle points up so it shares its base with the one below
+        let mut board = Binary::new_blank(4, 2).with_grid(Grid::Triangle);
+        board.trigger_index(0);
+        assert_board_eq(&board, &[1, 1, 0, 0, 1, 0, 0, 0]);
+
+        let mut board = Binary::new_blank(4, 2).with_grid(Grid::Triangle);
+        board.trigger_index(6);
+        assert_board_eq(&board, &[0, 0, 1, 0, 0, 1, 1, 1]);
+    }
+
+    #[test]
+    fn test_solvers_triangle() {
+        let mut board = Binary::new_blank(5, 3)
+            .with_target(&[])
+            .with_grid(Grid::Triangle);
+        board.trigger_index(1).trigger_index(12);
+
+        assert_registry_solves(&board, &["chase"]);
+    }
+
+    #[test]
+    fn board_simulate_cuboid() {
+        let mut board = Cuboid::new_blank(2, 2, 3);
+        board.trigger_index(5);
+
+        let expected = [
+            0, 1, //
+            0, 0, //
+            //
+            1, 1, //
+            0, 1, //
+            //
+            0, 1, //
+            0, 0, //
+        ];
+        assert_board_eq(&board, &expected);
+    }
+
+    #[test]
+    fn test_cube_faces_are_joined() {
+        for size in 1..5 {
+            let cube = Cuboid::new_cube(size);
+
+            for cell in 0..6 * size * size {
+                let toggled = cube.toggled_cells(cell);
+                assert_eq!(toggled.len(), 5, "cell {cell} of a {size} cube");
+
+                for neighbour in toggled {
+                    assert!(cube.toggled_cells(neighbour).contains(&cell));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_solvers_cuboid() {
+        for mut board in [Cuboid::new_blank(3, 3, 3), Cuboid::new_cube(3)] {
+            board = board.with_target(&[]);
+            board.trigger_index(1).trigger_index(20);
+
+            assert_registry_solves(&board, &["chase", "recursive"]);
+        }
+    }
+
+    #[test]
+    fn board_simulate_holes() {
+        // donut shaped board
+        let mut board = Binary::new_blank(3, 3).with_holes(&[4]);
+        board.trigger_index(1).trigger_index(4);
+
+        assert_board_eq(&board, &[1, 1, 1, 0, 0, 0, 0, 0, 0]);
+        assert!(!board.is_pressable(4));
+    }
+
+    #[test]
+    fn test_solvers_holes() {
+        let mut board = Binary::new_blank(4, 4)
+            .with_holes(&[0, 3, 12, 15])
+            .with_target(&[]);
+        board.trigger_index(1).trigger_index(6).trigger_index(14);
+
+        for (solver, solution) in assert_registry_solves(&board, &["chase"]) {
+            assert!(
+                solution.iter().all(|&press| board.is_pressable(press)),
+                "{} pressed a hole",
+                solver.name()
+            );
+        }
+    }
+
+    #[test]
+    fn test_gf2_solution_space_holes() {
+        let board = Binary::new_blank(4, 4).with_holes(&[0, 3, 12, 15]);
+        let space = gf2::solution_space(&board);
+
+        // without removing them every hole would double the solutions
+        for vector in &space.kernel {
+            assert!(vector.iter_ones().all(|press| board.is_pressable(press)));
+        }
+        assert_eq!(space.rank + space.kernel_dimension() + 4, 16);
+    }
+
+    #[test]
+    fn test_solvers_forbidden() {
+        let board = Binary::new_from_positions(&[3, 4, 5], 3, 3).with_forbidden(&[4]);
+
+        for (solver, solution) in assert_registry_solves(&board, &["chase"]) {
+            assert!(!solution.contains(&4), "{} pressed 4", solver.name());
+        }
+    }
+
+    #[test]
+    fn test_gf2_forbidden_unsolvable() {
+        // without the center every press toggles an even number of the cells of the plus
+        let board = Binary::new_from_positions(&[1, 3, 5, 7], 3, 3).with_forbidden(&[4]);
+
+        assert!(gf2::solve(&board).is_none());
+        assert!(!gf2::explain_unsolvable(&board).is_empty());
+    }
+
+    #[test]
+    fn test_solvers_weighted() {
+        let mut board = Binary::new_from_positions(&[0, 5, 10, 15], 4, 4);
+        let fewest = gf2::solve(&board).unwrap();
+
+        // make the fewest presses expensive so another solution becomes cheaper
+        let costs = (0..16)
+            .map(|index| if fewest.contains(&index) { 10 } else { 1 })
+            .collect::<Vec<_>>();
+        board = board.with_costs(&costs);
+        let cost = |solution: &[usize]| solution.iter().map(|&i| costs[i]).sum::<usize>();
+
+        let cheapest = gf2::solution_space(&board)
+            .solutions()
+            .map(|solution| cost(&solution))
+            .min()
+            .unwrap();
+        assert!(cheapest < cost(&fewest));
+
+        for (solver, solution) in assert_registry_solves(&board, &["zk"]) {
+            assert_eq!(cost(&solution), cheapest, "{} failed", solver.name());
+        }
+    }
+
+    #[test]
+    fn test_solver_lit_only() {
+        // every light off but the plus in the middle, pressing the centre turns all of them off
+        let board = Binary::new_from_positions(&[1, 3, 4, 5, 7], 3, 3).with_target(&[]);
+        assert_eq!(lit_only::solve(&board), Some(vec![4]));
+
+        // the lights at the sides need to be pressed again once they are lit again
+        let board = Binary::new_from_positions(&[0, 1], 3, 3).with_target(&[]);
+        let solution = lit_only::solve(&board).unwrap();
+        assert_eq!(solution.len(), 7);
+
+        let mut simulation = board.boxed_clone();
+        for &press in &solution {
+            assert_eq!(simulation.iter().nth(press), Some(&1), "{press} is off");
+            simulation.trigger_index(press);
+        }
+        assert!(simulation.is_solved());
+
+        // nothing can be pressed on a dark board
+        let board = Binary::new_blank(3, 3);
+        assert_eq!(lit_only::solve(&board), None);
+
+        assert!(solver::registry()
+            .iter()
+            .all(|solver| solver.name() != "lit-only"));
+    }
 }
@@ -0,0 +1,121 @@
+use std::str::FromStr;
+
+use crate::args::Wrap;
+
+/// Cells toggled by a press, as offsets from the pressed cell
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Neighbourhood {
+    /// The cell and the ones next to it vertically and horizontally
+    #[default]
+    Plus,
+    /// The cell and the ones next to it diagonally
+    Cross,
+    /// The cell and the 8 around it
+    Moore,
+    /// The cell and the ones a knight move away
+    Knight,
+    /// Any list of `(col, row)` offsets
+    Stencil(Vec<(isize, isize)>),
+}
+
+impl Neighbourhood {
+    #[must_use]
+    pub fn offsets(&self) -> Vec<(isize, isize)> {
+        match self {
+            Neighbourhood::Plus => vec![(0, 0), (0, -1), (-1, 0), (1, 0), (0, 1)],
+            Neighbourhood::Cross => vec![(0, 0), (-1, -1), (1, -1), (-1, 1), (1, 1)],
+            Neighbourhood::Moore => (-1..=1)
+                .flat_map(|row| (-1..=1).map(move |col| (col, row)))
+                .collect(),
+            Neighbourhood::Knight => vec![
+                (0, 0),
+                (-1, -2),
+                (1, -2),
+                (-2, -1),
+                (2, -1),
+                (-2, 1),
+                (2, 1),
+                (-1, 2),
+                (1, 2),
+            ],
+            Neighbourhood::Stencil(offsets) => offsets.clone(),
+        }
+    }
+
+    /// Mirrors the offsets, used to match the orientation of the indices
+    #[must_use]
+    pub fn flipped(&self, cols: bool, rows: bool) -> Neighbourhood {
+        if !cols && !rows {
+            return self.clone();
+        }
+
+        let flip = |value: isize, flip: bool| if flip { -value } else { value };
+        Neighbourhood::Stencil(
+            self.offsets()
+                .iter()
+                .map(|&(col, row)| (flip(col, cols), flip(row, rows)))
+                .collect(),
+        )
+    }
+
+    /// Cells toggled by pressing the cell at `index`, the offsets that fall outside the board are
+    /// dropped unless the edges wrap. Wrapping can reach the same cell twice but it's only toggled once
+    #[must_use]
+    pub fn cells(&self, cols: usize, rows: usize, wrap: Wrap, index: usize) -> Vec<usize> {
+        let col = (index % cols) as isize;
+        let row = (index / cols) as isize;
+        let (cols, rows) = (cols as isize, rows as isize);
+
+        let mut cells: Vec<usize> = vec![];
+        for (col_offset, row_offset) in self.offsets() {
+            let (mut col, mut row) = (col + col_offset, row + row_offset);
+
+            if wrap.horizontal() {
+                col = col.rem_euclid(cols);
+            }
+            if wrap.vertical() {
+                row = row.rem_euclid(rows);
+            }
+            if !(0..cols).contains(&col) || !(0..rows).contains(&row) {
+                continue;
+            }
+
+            let cell = (row * cols + col) as usize;
+            if !cells.contains(&cell) {
+                cells.push(cell);
+            }
+        }
+
+        cells
+    }
+}
+
+impl FromStr for Neighbourhood {
+    type Err = String;
+
+    /// Either the name of a neighbourhood or a stencil like `0,0;1,0;-1,0;0,2`
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim() {
+            "plus" => Ok(Neighbourhood::Plus),
+            "x" | "cross" => Ok(Neighbourhood::Cross),
+            "moore" => Ok(Neighbourhood::Moore),
+            "knight" => Ok(Neighbourhood::Knight),
+            stencil => stencil
+                .split(';')
+                .map(|offset| {
+                    let Some((col, row)) = offset.split_once(',') else {
+                        return Err(format!("Offset '{offset}' must be 'col,row'"));
+                    };
+                    let parse = |value: &str| {
+                        value
+                            .trim()
+                            .parse::<isize>()
+                            .map_err(|err| format!("Invalid offset '{offset}': {err}"))
+                    };
+                    Ok((parse(col)?, parse(row)?))
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .map(Neighbourhood::Stencil),
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use crate::args::Grid;
+
+use super::{
+    board::{Board, Rules},
+    grid,
+};
+
+/// Board where every cell has its own set of toggled cells, like Merlin's Magic Square
+#[derive(Debug, Clone)]
+pub struct PerCell {
+    cols: usize,
+    rows: usize,
+    board: Vec<usize>,
+    rules: Rules,
+    toggles: Vec<Vec<usize>>,
+    grid: Grid,
+}
+
+impl PerCell {
+    /// `toggles[index]` are the cells that change when pressing the cell at `index`
+    #[must_use]
+    pub fn new_blank(toggles: Vec<Vec<usize>>, cols: usize, rows: usize) -> PerCell {
+        assert_eq!(toggles.len(), cols * rows, "every cell needs its toggles");
+
+        let toggles = toggles
+            .into_iter()
+            .map(|mut cells| {
+                cells.sort_unstable();
+                cells.dedup();
+                cells
+            })
+            .collect();
+
+        PerCell {
+            cols,
+            rows,
+            board: vec![0usize; cols * rows],
+            rules: Rules::new(cols * rows),
+            toggles,
+            grid: Grid::Square,
+        }
+    }
+
+    #[must_use]
+    pub fn new_from_positions(
+        active: &[usize],
+        toggles: Vec<Vec<usize>>,
+        cols: usize,
+        rows: usize,
+    ) -> PerCell {
+        let mut per_cell = PerCell::new_blank(toggles, cols, rows);
+
+        active
+            .iter()
+            .for_each(|position| per_cell.board[*position] = 1);
+
+        per_cell
+    }
+
+    /// Shape of the cells, only used to draw the board
+    #[must_use]
+    pub fn with_grid(mut self, grid: Grid) -> PerCell {
+        self.grid = grid;
+
+        self
+    }
+
+    fn get_index(&self, col: usize, row: usize) -> usize {
+        row * self.cols + col
+    }
+}
+
+impl Board for PerCell {
+    fn size(&self) -> (usize, usize) {
+        (self.cols, self.rows)
+    }
+
+    fn cols(&self) -> usize {
+        self.cols
+    }
+
+    fn rows(&self) -> usize {
+        self.rows
+    }
+
+    fn iter(&self) -> std::slice::Iter<'_, usize> {
+        self.board.iter()
+    }
+
+    fn get(&self, col: usize, row: usize) -> Option<usize> {
+        if col < self.cols && row < self.rows {
+            Some(self.board[self.get_index(col, row)])
+        } else {
+            None
+        }
+    }
+
+    fn set(&mut self, col: usize, row: usize, value: usize) -> bool {
+        if col < self.cols && row < self.rows && value <= 1 {
+            let index = self.get_index(col, row);
+            self.board[index] = value;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn rules(&self) -> &Rules {
+        &self.rules
+    }
+
+    fn rules_mut(&mut self) -> &mut Rules {
+        &mut self.rules
+    }
+
+    fn states(&self) -> usize {
+        2
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Board> {
+        Box::new(self.clone())
+    }
+
+    fn trigger_index(&mut self, index: usize) -> &mut dyn Board {
+        if index >= self.board.len() {
+            return self;
+        }
+        for &cell in &self.toggles[index] {
+            self.board[cell] = (self.board[cell] + 1) % 2;
+        }
+        self
+    }
+
+    fn trigger_coord(&mut self, col: usize, row: usize) -> &mut dyn Board {
+        if col >= self.cols || row >= self.rows {
+            return self;
+        }
+        let index = self.get_index(col, row);
+        self.trigger_index(index)
+    }
+
+    fn toggled_cells(&self, index: usize) -> Vec<usize> {
+        self.toggles[index].clone()
+    }
+
+    fn draw(&self, cells: &[String]) -> String {
+        grid::draw(self.grid, self.cols, cells)
+    }
+}
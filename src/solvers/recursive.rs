@@ -1,18 +1,47 @@
-use super::board::{Binary, Board};
+use super::{
+    board::Board,
+    solver::{Capabilities, Solver},
+};
+
+/// Backtracking over every combination of presses
+pub struct RecursiveSolver;
+
+impl Solver for RecursiveSolver {
+    fn name(&self) -> &'static str {
+        "recursive"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            minimal: true,
+            max_cells: Some(16),
+            masks: true,
+            multi_state: false,
+            neighbourhoods: true,
+            restricted: true,
+            weighted: true,
+        }
+    }
+
+    fn solve(&self, board: &dyn Board) -> Option<Vec<usize>> {
+        solve(board)
+    }
+}
 
 pub fn solve(board: &dyn Board) -> Option<Vec<usize>> {
+    // the search only records the solutions found after a press
+    if board.is_solved() {
+        return Some(vec![]);
+    }
+
     let mut solution: Vec<usize> = vec![];
     let mut best_solution: Option<Vec<usize>> = None;
     let mut available_moves: Vec<bool> = vec![true; board.cols() * board.rows()];
 
-    let mut board = Binary::new_from_values(
-        &board.iter().map(|&b| b != 0).collect::<Vec<_>>(),
-        board.cols(),
-        board.rows(),
-    );
+    let mut board = board.boxed_clone();
 
     solve_recursive(
-        &mut board,
+        &mut *board,
         &mut available_moves,
         &mut solution,
         &mut best_solution,
@@ -21,8 +50,9 @@ pub fn solve(board: &dyn Board) -> Option<Vec<usize>> {
     best_solution
 }
 
-fn is_solution_better(new_solution: &[usize], old_solution: &[usize]) -> bool {
-    new_solution.len() < old_solution.len()
+fn is_solution_better(new_solution: &[usize], old_solution: &[usize], board: &dyn Board) -> bool {
+    let cost = |solution: &[usize]| -> usize { solution.iter().map(|&i| board.cost(i)).sum() };
+    cost(new_solution) < cost(old_solution)
 }
 
 fn solve_recursive(
@@ -31,16 +61,20 @@ fn solve_recursive(
     solution: &mut Vec<usize>,
     best_solution: &mut Option<Vec<usize>>,
 ) {
-    let is_better_solution = match best_solution {
-        Some(best) => is_solution_better(solution, best),
+    // once a solution is known only the branches that are still cheaper can improve it
+    let is_worse_solution = match best_solution {
+        Some(best) => !is_solution_better(solution, best, board),
         None => false,
     };
 
-    if board.is_solved() || solution.len() > board.iter().len() || is_better_solution {
+    if board.is_solved() || solution.len() > board.iter().len() || is_worse_solution {
         return;
     }
 
     for i in 0..(board.cols() * board.rows()) {
+        if !board.is_pressable(i) {
+            continue;
+        }
         if !available_moves[i] {
             return;
         }
@@ -57,7 +91,7 @@ fn solve_recursive(
                     *best_solution = Some(solution.clone());
                 }
                 Some(best) => {
-                    if is_solution_better(solution, best) {
+                    if is_solution_better(solution, best, board) {
                         *best_solution = Some(solution.clone());
                     }
                 }
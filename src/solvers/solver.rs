@@ -0,0 +1,97 @@
+use crate::args::{SolverKind, Wrap};
+
+use super::{
+    board::Board, chase::ChaseSolver, gf2::Gf2Solver, lit_only::LitOnlySolver,
+    neighbourhood::Neighbourhood, recursive::RecursiveSolver, zk::ZkSolver,
+};
+
+/// What a solver is able to handle, used to pick the right one for a board
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Capabilities {
+    /// Looks for the solution with the fewest presses, the solvers built on the kernel only
+    /// guarantee it when the kernel is small enough to be searched exhaustively
+    pub minimal: bool,
+    /// Largest number of cells that can be solved in a reasonable time
+    pub max_cells: Option<usize>,
+    /// Handles boards with don't care cells
+    pub masks: bool,
+    /// Handles boards with more than 2 states per cell
+    pub multi_state: bool,
+    /// Handles presses that don't follow the plus shape
+    pub neighbourhoods: bool,
+    /// Handles boards with cells that can't be pressed
+    pub restricted: bool,
+    /// Finds the cheapest solution when the presses have different costs
+    pub weighted: bool,
+}
+
+pub trait Solver {
+    fn name(&self) -> &'static str;
+    fn capabilities(&self) -> Capabilities;
+    fn solve(&self, board: &dyn Board) -> Option<Vec<usize>>;
+
+    fn can_solve(&self, board: &dyn Board) -> bool {
+        let cells = board.cols() * board.rows();
+        let capabilities = self.capabilities();
+
+        capabilities
+            .max_cells
+            .is_none_or(|max_cells| cells <= max_cells)
+            && (capabilities.masks || (0..cells).all(|cell| !board.is_masked(cell)))
+            && (capabilities.multi_state || board.states() == 2)
+            && (capabilities.neighbourhoods || is_plus_shaped(board))
+            && (capabilities.restricted || (0..cells).all(|cell| board.is_pressable(cell)))
+            && (capabilities.weighted || (0..cells).all(|cell| board.cost(cell) == board.cost(0)))
+    }
+}
+
+/// Every press toggles the cell and the ones next to it vertically and horizontally,
+/// no matter how the edges wrap
+#[must_use]
+pub fn is_plus_shaped(board: &dyn Board) -> bool {
+    let (cols, rows) = board.size();
+    let sorted = |mut cells: Vec<usize>| {
+        cells.sort_unstable();
+        cells
+    };
+
+    [Wrap::None, Wrap::Horizontal, Wrap::Vertical, Wrap::Both]
+        .into_iter()
+        .any(|wrap| {
+            (0..cols * rows).all(|index| {
+                sorted(board.toggled_cells(index))
+                    == sorted(Neighbourhood::Plus.cells(cols, rows, wrap, index))
+            })
+        })
+}
+
+/// Every available solver sorted by preference, the fastest first. The lit-only one is left
+/// out because it plays with different rules
+#[must_use]
+pub fn registry() -> Vec<Box<dyn Solver>> {
+    vec![
+        Box::new(ChaseSolver),
+        Box::new(Gf2Solver),
+        Box::new(RecursiveSolver),
+        Box::new(ZkSolver),
+    ]
+}
+
+#[must_use]
+pub fn from_kind(kind: SolverKind) -> Box<dyn Solver> {
+    match kind {
+        SolverKind::Gf2 => Box::new(Gf2Solver),
+        SolverKind::Chase => Box::new(ChaseSolver),
+        SolverKind::Recursive => Box::new(RecursiveSolver),
+        SolverKind::Zk => Box::new(ZkSolver),
+        SolverKind::LitOnly => Box::new(LitOnlySolver),
+    }
+}
+
+/// First solver of the registry able to handle the board
+#[must_use]
+pub fn select(board: &dyn Board) -> Option<Box<dyn Solver>> {
+    registry()
+        .into_iter()
+        .find(|solver| solver.can_solve(board))
+}
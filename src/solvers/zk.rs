@@ -0,0 +1,252 @@
+use super::{
+    board::Board,
+    solver::{Capabilities, Solver},
+};
+
+/// Gaussian elimination over the integers modulo the number of states
+pub struct ZkSolver;
+
+impl Solver for ZkSolver {
+    fn name(&self) -> &'static str {
+        "zk"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            minimal: false,
+            max_cells: Some(30 * 30),
+            masks: true,
+            multi_state: true,
+            neighbourhoods: true,
+            restricted: true,
+            weighted: false,
+        }
+    }
+
+    fn solve(&self, board: &dyn Board) -> Option<Vec<usize>> {
+        solve(board)
+    }
+}
+
+/// Solves the board over Z_k where k is the number of states. The system is split by the
+/// prime powers of k, each part is solved with its Smith normal form and joined back with the
+/// chinese remainder theorem. A cell pressed `n` times shows up `n` times in the solution
+pub fn solve(board: &dyn Board) -> Option<Vec<usize>> {
+    let states = board.states();
+    let cells = board.cols() * board.rows();
+    let values = board.iter().copied().collect::<Vec<_>>();
+
+    let constrained = (0..cells)
+        .filter(|&cell| !board.is_masked(cell))
+        .collect::<Vec<_>>();
+
+    let mut equation = vec![None; cells];
+    constrained
+        .iter()
+        .enumerate()
+        .for_each(|(position, &cell)| equation[cell] = Some(position));
+
+    let mut matrix = vec![vec![0usize; cells]; constrained.len()];
+    let toggles = (0..cells)
+        .filter(|&press| board.is_pressable(press))
+        .flat_map(|press| {
+            board
+                .toggled_cells(press)
+                .into_iter()
+                .map(move |cell| (cell, press))
+        });
+    for (cell, press) in toggles {
+        if let Some(row) = equation[cell] {
+            matrix[row][press] = (matrix[row][press] + 1) % states;
+        }
+    }
+
+    let expected = constrained
+        .iter()
+        .map(|&cell| (board.target(cell) + states - values[cell]) % states)
+        .collect::<Vec<_>>();
+
+    let mut presses = vec![0usize; cells];
+    let mut modulus = 1;
+    for (prime, power) in factorise(states) {
+        let prime_power = prime.pow(power);
+        let partial = solve_prime_power(&matrix, &expected, cells, prime, power)?;
+
+        for (press, value) in presses.iter_mut().zip(partial) {
+            *press = chinese_remainder(*press, modulus, value, prime_power);
+        }
+        modulus *= prime_power;
+    }
+
+    Some(
+        presses
+            .iter()
+            .enumerate()
+            .flat_map(|(index, &times)| std::iter::repeat_n(index, times))
+            .collect(),
+    )
+}
+
+/// Solves `A·x = b` modulo `prime^power` reducing `A` to its Smith normal form `D = U·A·V`.
+/// In this ring the entry with the smallest number of `prime` factors divides every other,
+/// using it as pivot both its row and its column can be cleared
+fn solve_prime_power(
+    matrix: &[Vec<usize>],
+    expected: &[usize],
+    cols: usize,
+    prime: usize,
+    power: u32,
+) -> Option<Vec<usize>> {
+    let modulus = prime.pow(power);
+    let rows = matrix.len();
+    let valuation = |mut value: usize| {
+        let mut factors = 0;
+        while value.is_multiple_of(prime) {
+            value /= prime;
+            factors += 1;
+        }
+        factors
+    };
+
+    let mut m = matrix
+        .iter()
+        .map(|row| row.iter().map(|value| value % modulus).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let mut b = expected
+        .iter()
+        .map(|value| value % modulus)
+        .collect::<Vec<_>>();
+
+    // keeps track of the column operations, x = V·y
+    let mut v = (0..cols)
+        .map(|row| (0..cols).map(|col| usize::from(row == col)).collect())
+        .collect::<Vec<Vec<usize>>>();
+
+    let mut pivots: Vec<usize> = vec![];
+
+    for t in 0..rows.min(cols) {
+        // 1. find the pivot with the smallest valuation
+        let mut best: Option<(usize, usize, u32)> = None;
+        'search: for (i, row) in m.iter().enumerate().skip(t) {
+            for (j, &value) in row.iter().enumerate().skip(t) {
+                if value == 0 {
+                    continue;
+                }
+                let factors = valuation(value);
+                if best.is_none_or(|(_, _, lowest)| factors < lowest) {
+                    best = Some((i, j, factors));
+                    if factors == 0 {
+                        break 'search;
+                    }
+                }
+            }
+        }
+        let Some((i, j, factors)) = best else {
+            break;
+        };
+
+        m.swap(t, i);
+        b.swap(t, i);
+        m.iter_mut().for_each(|row| row.swap(t, j));
+        v.iter_mut().for_each(|row| row.swap(t, j));
+
+        // 2. normalise the pivot to prime^factors
+        let pivot = prime.pow(factors);
+        let inverse = inverse(m[t][t] / pivot, modulus);
+        m[t].iter_mut()
+            .for_each(|value| *value = *value * inverse % modulus);
+        b[t] = b[t] * inverse % modulus;
+
+        // 3. clear the column below the pivot
+        for i in t + 1..rows {
+            if m[i][t] == 0 {
+                continue;
+            }
+            let factor = m[i][t] / pivot;
+            let (head, tail) = m.split_at_mut(i);
+            for (value, pivot_value) in tail[0][t..].iter_mut().zip(&head[t][t..]) {
+                *value = (*value + modulus - factor * pivot_value % modulus) % modulus;
+            }
+            b[i] = (b[i] + modulus - factor * b[t] % modulus) % modulus;
+        }
+
+        // 4. clear the row, the column below is 0 so only V changes
+        for j in t + 1..cols {
+            if m[t][j] == 0 {
+                continue;
+            }
+            let factor = m[t][j] / pivot;
+            m[t][j] = 0;
+            for row in &mut v {
+                row[j] = (row[j] + modulus - factor * row[t] % modulus) % modulus;
+            }
+        }
+
+        pivots.push(pivot);
+    }
+
+    // D·y = U·b
+    if b[pivots.len()..].iter().any(|&value| value != 0) {
+        return None;
+    }
+    let mut y = vec![0usize; cols];
+    for (t, &pivot) in pivots.iter().enumerate() {
+        if !b[t].is_multiple_of(pivot) {
+            return None;
+        }
+        y[t] = b[t] / pivot;
+    }
+
+    Some(
+        v.iter()
+            .map(|row| {
+                row.iter()
+                    .zip(y.iter())
+                    .fold(0, |acc, (a, b)| (acc + a * b) % modulus)
+            })
+            .collect(),
+    )
+}
+
+/// Prime factors of `value` with their power
+fn factorise(mut value: usize) -> Vec<(usize, u32)> {
+    let mut factors = vec![];
+    let mut prime = 2;
+
+    while prime * prime <= value {
+        let mut power = 0;
+        while value.is_multiple_of(prime) {
+            value /= prime;
+            power += 1;
+        }
+        if power > 0 {
+            factors.push((prime, power));
+        }
+        prime += 1;
+    }
+    if value > 1 {
+        factors.push((value, 1));
+    }
+
+    factors
+}
+
+/// Inverse of a unit modulo `modulus` using the extended euclidean algorithm
+fn inverse(value: usize, modulus: usize) -> usize {
+    let (mut old_r, mut r) = (value as i128, modulus as i128);
+    let (mut old_s, mut s) = (1i128, 0i128);
+
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_s, s) = (s, old_s - quotient * s);
+    }
+
+    old_s.rem_euclid(modulus as i128) as usize
+}
+
+/// Value congruent with `a` modulo `m` and with `b` modulo `n`, being `m` and `n` coprime
+fn chinese_remainder(a: usize, m: usize, b: usize, n: usize) -> usize {
+    let step = (b + n - a % n) % n * inverse(m % n, n) % n;
+    a + m * step
+}